@@ -173,6 +173,96 @@ describe('spreadUpdate', () => {
     `);
   });
 
+  it('attributes after spread', async function() {
+    let patch;
+    let setSpread_;
+    function Comp() {
+      const [spread, setSpread] = useState({
+        id: 'spread_id',
+        className: 'spread_class',
+        name: 'spread_name',
+      });
+      setSpread_ = setSpread;
+      const cls = 'dynamic_class';
+      return (
+        <view>
+          <text {...spread} id='static_id' className={cls}>1</text>
+        </view>
+      );
+    }
+
+    globalEnvManager.switchToMainThread();
+    render(<Comp />, scratch);
+    expect(scratch.__element_root).toMatchInlineSnapshot(`
+      <page
+        cssId="default-entry-from-native:0"
+      >
+        <view>
+          <text
+            class="dynamic_class"
+            flatten={false}
+            id="static_id"
+            name="spread_name"
+          >
+            <raw-text
+              text="1"
+            />
+          </text>
+        </view>
+      </page>
+    `);
+    globalEnvManager.switchToBackground();
+    render(<Comp />, scratchBackground);
+    patch = hydrate(JSON.parse(JSON.stringify(scratch)), scratchBackground);
+    globalEnvManager.switchToMainThread();
+    snapshotPatchApply(patch);
+    globalEnvManager.switchToBackground();
+    initGlobalSnapshotPatch();
+
+    setSpread_({
+      id: 'spread_id_2',
+      className: 'spread_class_2',
+      name: 'spread_name',
+    });
+    render(<Comp />, scratchBackground);
+    patch = takeGlobalSnapshotPatch();
+    expect(patch).toMatchInlineSnapshot(`
+      [
+        3,
+        -2,
+        0,
+        {
+          "className": "spread_class_2",
+          "flatten": false,
+          "id": "spread_id_2",
+          "name": "spread_name",
+        },
+      ]
+    `);
+
+    // The attributes after the spread still win.
+    globalEnvManager.switchToMainThread();
+    snapshotPatchApply(patch);
+    expect(scratch.__element_root).toMatchInlineSnapshot(`
+      <page
+        cssId="default-entry-from-native:0"
+      >
+        <view>
+          <text
+            class="dynamic_class"
+            flatten={false}
+            id="static_id"
+            name="spread_name"
+          >
+            <raw-text
+              text="1"
+            />
+          </text>
+        </view>
+      </page>
+    `);
+  });
+
   it('update', async function() {
     let patch;
     let setSpread_;
@@ -35,6 +35,24 @@ export const enum DynamicPartType {
   MultiChildren, // Multiple children updates (compat layer)
 }
 
+/**
+ * The helpers of {@link Snapshot.render} to render the dynamic parts of a snapshot to markup.
+ */
+export interface SnapshotRenderer {
+  /** Renders an attribute, and nothing for `null`, `undefined` or `false`. */
+  attr(name: string, value: unknown): string;
+  /**
+   * Renders the `style` with a dynamic part. `keys` are the properties of the values array of the
+   * extracted styles, and the values of its holes are skipped. The `value` is the style itself
+   * when `keys` is omitted.
+   */
+  style(staticStyle: string, value: unknown, keys?: (string | undefined)[]): string;
+  /** Renders the attributes of a spread, except the `excludedKeys`. */
+  spread(value: Record<string, unknown>, excludedKeys: string[]): string;
+  /** Renders the children of the `index`-th slot. */
+  slot(ctx: SnapshotInstance, index: number): string;
+}
+
 /**
  * A snapshot definition that contains all the information needed to create and update elements
  * This is generated at compile time through static analysis of the JSX
@@ -43,6 +61,10 @@ interface Snapshot {
   create: null | ((ctx: SnapshotInstance) => FiberElement[]);
   update: null | ((ctx: SnapshotInstance, index: number, oldValue: any) => void)[];
   slot: [DynamicPartType, number][];
+  /**
+   * Renders the snapshot to markup on the server, it only exists in the bundles of the `SSR` target.
+   */
+  render?: ((ctx: SnapshotInstance, r: SnapshotRenderer) => string) | undefined;
 
   isListHolder?: boolean;
   cssId?: number | undefined;
@@ -188,6 +210,7 @@ export function createSnapshot(
   slot: Snapshot['slot'],
   cssId?: number,
   entryName?: string,
+  render?: Snapshot['render'],
 ): string {
   if (
     __DEV__ && __JS__
@@ -219,7 +242,7 @@ export function createSnapshot(
     uniqID = `${entryName}:${uniqID}`;
   }
 
-  const s: Snapshot = { create, update, slot, cssId, entryName };
+  const s: Snapshot = { create, update, slot, cssId, entryName, render };
   snapshotManager.values.set(uniqID, s);
   if (slot && slot[0] && slot[0][0] === DynamicPartType.ListChildren) {
     s.isListHolder = true;
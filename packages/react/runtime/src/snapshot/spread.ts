@@ -36,7 +36,17 @@ const noFlattenAttributes = /* @__PURE__ */ new Set<string>([
   'exposure-id',
 ]);
 
-function updateSpread(snapshot: SnapshotInstance, index: number, oldValue: any, elementIndex: number): void {
+/**
+ * `excludedKeys` are owned by the attributes after the spread, which are compiled and updated
+ * separately, e.g.: `<view {...props} className="foo" />`.
+ */
+function updateSpread(
+  snapshot: SnapshotInstance,
+  index: number,
+  oldValue: any,
+  elementIndex: number,
+  excludedKeys?: string[],
+): void {
   oldValue ||= {};
   let newValue: Record<string, any> = snapshot.__values![index]; // compiler guarantee this must be an object;
 
@@ -80,6 +90,9 @@ function updateSpread(snapshot: SnapshotInstance, index: number, oldValue: any,
   const dataset: Record<string, any> = {};
   let match: RegExpMatchArray | null = null;
   for (const key in newValue) {
+    if (excludedKeys?.includes(key)) {
+      continue;
+    }
     const v = newValue[key];
     if (v !== oldValue[key]) {
       if (key === 'className') {
@@ -169,6 +182,9 @@ function updateSpread(snapshot: SnapshotInstance, index: number, oldValue: any,
 
   let hasOldDataset = false;
   for (const key in oldValue) {
+    if (excludedKeys?.includes(key)) {
+      continue;
+    }
     if (!(key in newValue)) {
       if (key === 'className') {
         __SetClasses(snapshot.__elements[elementIndex]!, '');
@@ -5,7 +5,11 @@
 import { formatMessages } from 'esbuild';
 import { describe, expect, it } from 'vitest';
 
-import { transformBundleResult, transformReactLynx } from '../main.js';
+import {
+  transformBundleResult,
+  transformReactLynx,
+  transformReactLynxBatch,
+} from '../main.js';
 
 describe('shake', () => {
   it('should match', async () => {
@@ -144,6 +148,7 @@ describe('jsx', () => {
       });
       ",
         "errors": [],
+        "fixes": [],
         "warnings": [],
       }
     `);
@@ -169,6 +174,7 @@ describe('errors and warnings', () => {
             "text": "Unexpected token \`view\`. Expected jsx identifier",
           },
         ],
+        "fixes": [],
         "warnings": [],
       }
     `);
@@ -207,8 +213,23 @@ Component, View
       Component, View;
       ",
         "errors": [],
+        "fixes": [
+          {
+            "edits": [
+              {
+                "end": 153,
+                "file": "",
+                "replacement": ""@lynx-js/react"",
+                "start": 129,
+              },
+            ],
+            "id": "react-lynx-deprecated-runtime-pkg",
+            "text": "use the new runtime package",
+          },
+        ],
         "warnings": [
           {
+            "id": "react-lynx-deprecated-components-pkg",
             "location": {
               "column": 0,
               "file": "",
@@ -220,6 +241,7 @@ Component, View
             "text": "DEPRECATED: old package "@lynx-js/react-components" is removed",
           },
           {
+            "id": "react-lynx-deprecated-runtime-pkg",
             "location": {
               "column": 0,
               "file": "",
@@ -227,6 +249,19 @@ Component, View
               "line": 4,
               "lineText": "import { Component } from "@lynx-js/react-runtime";",
             },
+            "notes": [
+              {
+                "location": {
+                  "column": 26,
+                  "file": "",
+                  "length": 24,
+                  "line": 4,
+                  "lineText": "import { Component } from "@lynx-js/react-runtime";",
+                  "suggestion": ""@lynx-js/react"",
+                },
+                "text": "use the new runtime package",
+              },
+            ],
             "pluginName": "",
             "text": "DEPRECATED: old runtime package "@lynx-js/react-runtime" is changed to "@lynx-js/react"",
           },
@@ -383,7 +418,7 @@ Component, View
     refresh: false,
   });
 
-  it('should error when encounter <component/>', async () => {
+  it('should not error when encounter <component/>', async () => {
     const { formatMessages } = await import('esbuild');
 
     const cfg = __cfg();
@@ -1580,3 +1615,20 @@ function getCurrentDelta(event) {
     `);
   });
 });
+
+describe('batch', () => {
+  it('should transform items in input order', async () => {
+    const items = Array.from({ length: 8 }, (_, i) => ({
+      filename: `${i}.js`,
+      code: `export const v${i} = ${i};`,
+    }));
+
+    const outputs = await transformReactLynxBatch(items);
+
+    expect(outputs).toHaveLength(8);
+    outputs.forEach((output, i) => {
+      expect(output.errors).toEqual([]);
+      expect(output.code).toContain(`export const v${i} = ${i};`);
+    });
+  });
+});
@@ -1,8 +1,7 @@
 use std::env;
 
-extern crate napi_build;
-
 fn main() {
+  #[cfg(feature = "napi")]
   napi_build::setup();
 
   if env::var("CARGO_CFG_TARGET_OS").unwrap() == "linux" {
@@ -8,8 +8,22 @@ export interface TransformNodiffBundleOptions {
   pluginName: string
   sourceFileName?: string
   sourcemap: boolean | string
+  /**
+   * @public
+   * The source map of the input code, generated by the previous loaders.
+   * Use `true` to read the inline `//# sourceMappingURL=data:...` of the input code.
+   */
+  inputSourceMap?: boolean | string
   extractStr: boolean | ExtractStrConfig
   minify?: boolean
+  /**
+   * @public
+   * Merge the snapshots with the same uid in one scope, see `JsxTransformerConfig.fingerprintUid`.
+   * Only the modules concatenated into one scope are merged, e.g.: by
+   * `optimization.concatenateModules`, the modules wrapped in their own factory functions are left
+   * as they are.
+   */
+  dedupeSnapshots?: boolean
 }
 export interface TransformNodiffBundleOutput {
   code: string
@@ -44,6 +58,23 @@ export interface PartialLocation {
   lineText?: string
   suggestion?: string
 }
+/** A fix of a diagnostic that can be applied without human review. */
+export interface Fix {
+  /** The id of the diagnostic, e.g.: `react-lynx-deprecated-lynx-key`. */
+  id?: string
+  text: string
+  edits: Array<TextEdit>
+}
+/**
+ * Replace `start..end` of `file` with `replacement`.
+ * The range is in UTF-8 byte offsets of the input code.
+ */
+export interface TextEdit {
+  file: string
+  start: number
+  end: number
+  replacement: string
+}
 export interface DarkModeConfig {
   /** @public */
   themeExpr: string
@@ -54,7 +85,7 @@ export interface AddComponentElementConfig {
 }
 export interface CompatVisitorConfig {
   /** @internal */
-  target: 'LEPUS' | 'JS' | 'MIXED'
+  target: 'LEPUS' | 'JS' | 'MIXED' | 'SSR'
   /** @public */
   componentsPkg: Array<string>
   /** @public */
@@ -95,7 +126,7 @@ export interface DefineDceVisitorConfig {
 }
 export interface DirectiveDceVisitorConfig {
   /** @internal */
-  target: 'LEPUS' | 'JS' | 'MIXED'
+  target: 'LEPUS' | 'JS' | 'MIXED' | 'SSR'
 }
 export interface DynamicImportVisitorConfig {
   /** @internal */
@@ -103,6 +134,10 @@ export interface DynamicImportVisitorConfig {
   /** @internal */
   layer: string
 }
+export interface DynamicImportManifest {
+  specifier: string
+  layer: string
+}
 export interface ExtractStrConfig {
   /** @public */
   strLength: number
@@ -123,15 +158,109 @@ export interface ShakeVisitorConfig {
   /** @public */
   removeCallParams: Array<string>
 }
+export interface SnapshotElementManifest {
+  /** The index of the element in `ctx.__elements`. */
+  index: number
+  tag: string
+  /** The content of the `raw-text` element. */
+  text?: string
+  children: Array<SnapshotElementManifest>
+}
+export interface DynamicPartManifest {
+  kind: 'attr' | 'spread' | 'slot' | 'children' | 'listChildren' | 'switch'
+  elementIndex: number
+  /** The attribute name of an `attr` dynamic part. */
+  attr?: string
+}
+export interface SnapshotManifest {
+  uid: string
+  /** The structural fingerprint, the snapshots with the same fingerprint share one definition. */
+  fingerprint: string
+  elements: Array<SnapshotElementManifest>
+  dynamicParts: Array<DynamicPartManifest>
+}
+/**
+ * A native element with a dedicated Element PAPI creator.
+ *
+ * ```json
+ * { "map": { "creator": "__CreateMap", "creationAttributes": ["provider"], "children": "none" } }
+ * ```
+ *
+ * `<map provider="amap" zoom={zoom} />` is created with `__CreateMap(pageId, { provider: "amap" })`,
+ * and `zoom` is set as usual.
+ */
+export interface ElementConfig {
+  /**
+   * @public
+   * The function to create the element, which is called with `pageId` and the creation attributes.
+   */
+  creator: string
+  /**
+   * @public
+   * The attributes that are passed to `creator` instead of being set after the creation.
+   * They must be static.
+   */
+  creationAttributes?: Array<string>
+  /**
+   * @public
+   * The children that the element accepts, defaults to `any`.
+   */
+  children?: 'any' | 'text' | 'none'
+}
+/**
+ * How `<component>` is created with `__CreateComponent`.
+ *
+ * `<component name="header" is="./header" />` is created with
+ * `__CreateComponent(pageId, "", 0, "", "header", "./header", {}, {})`.
+ */
+export interface ComponentConfig {
+  /**
+   * @public
+   * The attribute used as the `name` of the component, defaults to `name`.
+   */
+  nameAttribute?: string
+  /**
+   * @public
+   * The attribute used as the `path` of the component, defaults to `is`.
+   */
+  pathAttribute?: string
+}
 export interface JsxTransformerConfig {
   preserveJsx: boolean
   runtimePkg: string
   jsxImportSource?: string
   filename: string
   /** @internal */
-  target: 'LEPUS' | 'JS' | 'MIXED'
+  target: 'LEPUS' | 'JS' | 'MIXED' | 'SSR'
   /** @internal */
   isDynamicComponent?: boolean
+  /**
+   * @public
+   * Use the structural fingerprint of a snapshot as its uid, so that the identical snapshots of
+   * concatenated modules can be merged by `transformBundleResult` with `dedupeSnapshots`.
+   */
+  fingerprintUid?: boolean
+  /**
+   * @public
+   * The name of the entry, which is a part of the snapshot uids.
+   */
+  entryName?: string
+  /**
+   * @public
+   * The custom native elements with dedicated creators, by tag name.
+   */
+  elements?: Record<string, ElementConfig>
+  /**
+   * @public
+   * How `<component>` is created.
+   */
+  component?: ComponentConfig
+  /**
+   * @public
+   * Attach the source location of the JSX to each created element in development mode, e.g.:
+   * `{ file: "src/App.jsx", line: 3, column: 5, component: "App" }`, as `__lynx_source_location`.
+   */
+  sourceLocations?: boolean
 }
 export interface WorkletVisitorConfig {
   /**
@@ -146,8 +275,42 @@ export interface WorkletVisitorConfig {
   /** @internal */
   filename: string
   /** @internal */
-  target: 'LEPUS' | 'JS' | 'MIXED'
+  target: 'LEPUS' | 'JS' | 'MIXED' | 'SSR'
   runtimePkg: string
+  /**
+   * @public
+   * Derive the hash of a worklet from its code and the identifiers it captures, instead of the
+   * file and its position in the file. The hash is then stable across builds, and the identical
+   * worklets of different files share one hash.
+   */
+  contentAddressedHash?: boolean
+  /**
+   * @public
+   * The directives of the custom worklet kinds, in addition to `"main thread"` and
+   * `"use worklet"`. On the main thread, the worklets of a custom kind are registered by its own
+   * function instead of `registerWorkletInternal`.
+   */
+  directives?: Array<WorkletDirective>
+}
+export interface WorkletDirective {
+  /** The directive of the worklets, e.g.: `"use animation"`. */
+  directive: string
+  /**
+   * The type of the worklets, which is passed to the registration function and recorded in the
+   * manifest, e.g.: `"animation"`.
+   */
+  typeStr: string
+  /** The function to register the worklets with, e.g.: `registerAnimationWorklet`. */
+  register: string
+  /** The module to import the registration function from. */
+  importSource: string
+}
+export interface WorkletManifest {
+  hash: string
+  /** The `typeStr` of a custom worklet kind, or one of the built-in types. */
+  workletType: 'main-thread' | 'ui' | (string & {})
+  /** The identifiers captured from the outer scope, which will be passed with `_c`. */
+  capturedIdents: Array<string>
 }
 export interface TransformNodiffOptions {
   /**
@@ -156,11 +319,27 @@ export interface TransformNodiffOptions {
    */
   mode?: 'production' | 'development' | 'test'
   pluginName: string
+  /**
+   * @public
+   * Override the severity of the diagnostics by id, e.g.: `{ "react-lynx-extract-css": "off" }`.
+   */
+  diagnostics?: Record<string, 'off' | 'warn' | 'error'>
+  /**
+   * @public
+   * Report the warnings as errors, except the ones configured in `diagnostics`.
+   */
+  allWarningsAsErrors?: boolean
   filename: string
   sourceFileName?: string
   sourcemap: boolean | string
   sourceMapColumns?: boolean
   inlineSourcesContent?: boolean
+  /**
+   * @public
+   * The source map of the input code, generated by the previous loaders.
+   * Use `true` to read the inline `//# sourceMappingURL=data:...` of the input code.
+   */
+  inputSourceMap?: boolean | string
   /**
    * @public
    * This is swc syntax config in JSON format
@@ -179,13 +358,43 @@ export interface TransformNodiffOptions {
   /** @internal */
   inject?: boolean | InjectVisitorConfig
 }
+/** The structured description of what the transform generated for a module. */
+export interface TransformNodiffManifest {
+  snapshots: Array<SnapshotManifest>
+  worklets: Array<WorkletManifest>
+  dynamicImports: Array<DynamicImportManifest>
+}
 export interface TransformNodiffOutput {
   code: string
   map?: string
+  /** `None` when the module failed to parse or print. */
+  manifest?: TransformNodiffManifest
   errors: Array<PartialMessage>
   warnings: Array<PartialMessage>
+  /** The machine-applicable fixes of the diagnostics above. */
+  fixes: Array<Fix>
+}
+/** The outputs of a dual transform, one for each thread. */
+export interface TransformNodiffDualOutput {
+  /** The output of the main thread, i.e.: `target: 'LEPUS'`. */
+  lepus: TransformNodiffOutput
+  /** The output of the background thread, i.e.: `target: 'JS'`. */
+  js: TransformNodiffOutput
+}
+export interface TransformBatchItem {
+  /**
+   * @public
+   * The filename of the module, this overrides the `filename` in `options`.
+   */
+  filename: string
+  code: string
+  options?: TransformNodiffOptions
 }
 export function transformReactLynxSync(code: string, options?: TransformNodiffOptions | undefined | null): TransformNodiffOutput
 export function transformReactLynx(code: string, options?: TransformNodiffOptions | undefined | null): Promise<TransformNodiffOutput>
+export function transformReactLynxBatchSync(items: Array<TransformBatchItem>): Array<TransformNodiffOutput>
+export function transformReactLynxBatch(items: Array<TransformBatchItem>): Promise<Array<TransformNodiffOutput>>
+export function transformReactLynxDualSync(code: string, options?: TransformNodiffOptions | undefined | null): TransformNodiffDualOutput
+export function transformReactLynxDual(code: string, options?: TransformNodiffOptions | undefined | null): Promise<TransformNodiffDualOutput>
 export function transformBundleResultSync(code: string, options?: TransformNodiffBundleOptions | undefined | null): TransformNodiffBundleOutput
 export function transformBundleResult(code: string, options?: TransformNodiffBundleOptions | undefined | null): Promise<TransformNodiffBundleOutput>
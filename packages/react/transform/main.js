@@ -12,4 +12,8 @@ export const {
   transformBundleResultSync,
   transformReactLynx,
   transformBundleResult,
+  transformReactLynxBatchSync,
+  transformReactLynxBatch,
+  transformReactLynxDualSync,
+  transformReactLynxDual,
 } = process.env['USE_NAPI'] ? require('./index.cjs') : exports;
@@ -0,0 +1,42 @@
+//! A no-op `#[napi]` attribute, used by `react-transform` when the `napi` feature is disabled.
+//!
+//! The fields of a `#[napi(object)]` struct are annotated with `#[napi(ts_type = "...")]`, which
+//! is only valid inside the napi macro. `cfg_attr` can not be used on those fields, because the
+//! input of an attribute macro is not configured before the expansion. So this macro strips all
+//! the `#[napi(...)]` attributes of the item instead.
+
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+
+#[proc_macro_attribute]
+pub fn napi(_attr: TokenStream, input: TokenStream) -> TokenStream {
+  strip_napi_attrs(input)
+}
+
+fn strip_napi_attrs(input: TokenStream) -> TokenStream {
+  let mut output = vec![];
+  let mut tokens = input.into_iter().peekable();
+  while let Some(token) = tokens.next() {
+    match token {
+      TokenTree::Punct(punct) if punct.as_char() == '#' => {
+        if matches!(tokens.peek(), Some(TokenTree::Group(attr)) if is_napi_attr(attr)) {
+          tokens.next();
+          continue;
+        }
+        output.push(TokenTree::Punct(punct));
+      }
+      TokenTree::Group(group) => {
+        let mut stripped = Group::new(group.delimiter(), strip_napi_attrs(group.stream()));
+        stripped.set_span(group.span());
+        output.push(TokenTree::Group(stripped));
+      }
+      token => output.push(token),
+    }
+  }
+  output.into_iter().collect()
+}
+
+/// `[napi]` or `[napi(...)]`
+fn is_napi_attr(attr: &Group) -> bool {
+  attr.delimiter() == Delimiter::Bracket
+    && matches!(attr.stream().into_iter().next(), Some(TokenTree::Ident(ident)) if ident.to_string() == "napi")
+}
@@ -0,0 +1,234 @@
+//! A command-line driver of the ReactLynx transform.
+//!
+//! It runs the same pipeline as `transformReactLynx` (or `transformBundleResult` with `--bundle`)
+//! on files, which makes it easy to debug the compiler output without a JS toolchain.
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::ExitCode,
+};
+
+use react_transform::{
+  apply_fixes, transform_bundle_result_inner, transform_react_lynx_batch_inner, TransformBatchItem,
+  TransformNodiffBundleOptions, TransformNodiffOptions,
+};
+use serde_json::{json, Value};
+
+const USAGE: &str = "\
+Usage: react-transform [OPTIONS] <FILE|GLOB>...
+
+Options:
+  -c, --options <FILE>  Options in JSON. The shape is the same as `TransformNodiffOptions`,
+                        or `TransformNodiffBundleOptions` when `--bundle` is given
+      --bundle          Run `transformBundleResult` instead of `transformReactLynx`
+  -o, --out-dir <DIR>   Write the code and `.map` files to DIR, print the code to stdout if omitted
+      --fix             Apply the machine-applicable fixes to the input files
+  -h, --help            Print help
+
+Diagnostics are printed to stderr as esbuild `PartialMessage`s in JSON.";
+
+struct Args {
+  options: Option<PathBuf>,
+  bundle: bool,
+  out_dir: Option<PathBuf>,
+  fix: bool,
+  inputs: Vec<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+  let mut args = Args {
+    options: None,
+    bundle: false,
+    out_dir: None,
+    fix: false,
+    inputs: vec![],
+  };
+
+  let mut iter = std::env::args().skip(1);
+  while let Some(arg) = iter.next() {
+    match arg.as_str() {
+      "-c" | "--options" => {
+        args.options = Some(iter.next().ok_or("`--options` requires a value")?.into());
+      }
+      "-o" | "--out-dir" => {
+        args.out_dir = Some(iter.next().ok_or("`--out-dir` requires a value")?.into());
+      }
+      "--bundle" => args.bundle = true,
+      "--fix" => args.fix = true,
+      "-h" | "--help" => {
+        println!("{}", USAGE);
+        std::process::exit(0);
+      }
+      _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+      _ => args.inputs.push(arg),
+    }
+  }
+
+  if args.inputs.is_empty() {
+    return Err("no input files".into());
+  }
+  if args.fix && args.bundle {
+    return Err("`--fix` can not be used with `--bundle`".into());
+  }
+
+  Ok(args)
+}
+
+fn expand_inputs(inputs: &[String]) -> Result<Vec<PathBuf>, String> {
+  let mut files = vec![];
+  for input in inputs {
+    if !input.contains(['*', '?', '[']) {
+      files.push(PathBuf::from(input));
+      continue;
+    }
+
+    let matched = glob::glob(input)
+      .map_err(|err| format!("invalid glob `{}`: {}", input, err))?
+      .filter_map(Result::ok)
+      .filter(|path| path.is_file())
+      .collect::<Vec<_>>();
+    if matched.is_empty() {
+      return Err(format!("no files matched `{}`", input));
+    }
+    files.extend(matched);
+  }
+  Ok(files)
+}
+
+fn read_options<T>(path: &Option<PathBuf>) -> Result<T, String>
+where
+  T: Default + serde::de::DeserializeOwned,
+{
+  match path {
+    None => Ok(T::default()),
+    Some(path) => {
+      let content = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read `{}`: {}", path.display(), err))?;
+      serde_json::from_str(&content)
+        .map_err(|err| format!("failed to parse `{}`: {}", path.display(), err))
+    }
+  }
+}
+
+/// The output path of `input`, relative inputs keep their directories under `out_dir`.
+fn output_path(out_dir: &Path, input: &Path) -> PathBuf {
+  if input.is_relative() && input.components().all(|c| c.as_os_str() != "..") {
+    out_dir.join(input)
+  } else {
+    out_dir.join(input.file_name().unwrap_or_default())
+  }
+}
+
+fn write_output(
+  out_dir: &Option<PathBuf>,
+  input: &Path,
+  code: &str,
+  map: Option<&str>,
+) -> Result<(), String> {
+  let Some(out_dir) = out_dir else {
+    println!("{}", code);
+    return Ok(());
+  };
+
+  let output = output_path(out_dir, input);
+  if let Some(parent) = output.parent() {
+    fs::create_dir_all(parent)
+      .map_err(|err| format!("failed to create `{}`: {}", parent.display(), err))?;
+  }
+  fs::write(&output, code)
+    .map_err(|err| format!("failed to write `{}`: {}", output.display(), err))?;
+
+  if let Some(map) = map {
+    let mut map_path = output.into_os_string();
+    map_path.push(".map");
+    fs::write(&map_path, map).map_err(|err| {
+      format!(
+        "failed to write `{}`: {}",
+        Path::new(&map_path).display(),
+        err
+      )
+    })?;
+  }
+  Ok(())
+}
+
+fn run(args: Args) -> Result<bool, String> {
+  let files = expand_inputs(&args.inputs)?;
+  let sources = files
+    .iter()
+    .map(|file| {
+      fs::read_to_string(file)
+        .map_err(|err| format!("failed to read `{}`: {}", file.display(), err))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let mut errors: Vec<Value> = vec![];
+  let mut warnings: Vec<Value> = vec![];
+
+  if args.bundle {
+    let options: TransformNodiffBundleOptions = read_options(&args.options)?;
+    for (file, code) in files.iter().zip(sources) {
+      let output = transform_bundle_result_inner(
+        code,
+        TransformNodiffBundleOptions {
+          filename: file.to_string_lossy().into_owned(),
+          ..options.clone()
+        },
+      );
+      write_output(&args.out_dir, file, &output.code, output.map.as_deref())?;
+      errors.extend(output.errors.iter().map(|e| json!(e)));
+      warnings.extend(output.warnings.iter().map(|w| json!(w)));
+    }
+  } else {
+    let options: TransformNodiffOptions = read_options(&args.options)?;
+    let items = files
+      .iter()
+      .zip(&sources)
+      .map(|(file, code)| TransformBatchItem {
+        filename: file.to_string_lossy().into_owned(),
+        code: code.clone(),
+        options: Some(options.clone()),
+      })
+      .collect();
+
+    let outputs = transform_react_lynx_batch_inner(items);
+    for ((file, code), output) in files.iter().zip(&sources).zip(outputs) {
+      if args.fix && !output.fixes.is_empty() {
+        fs::write(file, apply_fixes(code, &output.fixes))
+          .map_err(|err| format!("failed to write `{}`: {}", file.display(), err))?;
+      }
+      write_output(&args.out_dir, file, &output.code, output.map.as_deref())?;
+      errors.extend(output.errors.iter().map(|e| json!(e)));
+      warnings.extend(output.warnings.iter().map(|w| json!(w)));
+    }
+  }
+
+  let has_errors = !errors.is_empty();
+  if has_errors || !warnings.is_empty() {
+    eprintln!(
+      "{}",
+      serde_json::to_string_pretty(&json!({ "errors": errors, "warnings": warnings })).unwrap()
+    );
+  }
+  Ok(!has_errors)
+}
+
+fn main() -> ExitCode {
+  let args = match parse_args() {
+    Ok(args) => args,
+    Err(err) => {
+      eprintln!("error: {}\n\n{}", err, USAGE);
+      return ExitCode::from(2);
+    }
+  };
+
+  match run(args) {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::FAILURE,
+    Err(err) => {
+      eprintln!("error: {}", err);
+      ExitCode::from(2)
+    }
+  }
+}
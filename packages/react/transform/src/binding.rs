@@ -0,0 +1,191 @@
+//! The napi binding of the transform, see `index.d.ts` for the JS API.
+
+use napi::{bindgen_prelude::AsyncTask, Env, Task};
+use swc_core::common::Globals;
+
+use crate::{
+  bundle, transform_react_lynx_batch_inner, transform_react_lynx_dual_inner,
+  transform_react_lynx_inner, TransformBatchItem, TransformNodiffDualOutput,
+  TransformNodiffOptions, TransformNodiffOutput,
+};
+
+pub struct TransformTask {
+  pub code: String,
+  pub options: TransformNodiffOptions,
+}
+
+pub struct DualTransformTask {
+  pub code: String,
+  pub options: TransformNodiffOptions,
+}
+
+pub struct BatchTransformTask {
+  pub items: Vec<TransformBatchItem>,
+}
+
+#[napi]
+pub fn transform_react_lynx_sync(
+  _env: Env,
+  code: String,
+  options: Option<TransformNodiffOptions>,
+) -> napi::Result<TransformNodiffOutput> {
+  let out = transform_react_lynx_inner(&Globals::new(), code, options.unwrap_or_default());
+  napi::Result::Ok(out)
+}
+
+#[napi]
+impl Task for TransformTask {
+  type Output = TransformNodiffOutput;
+  type JsValue = TransformNodiffOutput;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let out = transform_react_lynx_inner(&Globals::new(), self.code.clone(), self.options.clone());
+    Ok(out)
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+}
+
+#[napi]
+pub fn transform_react_lynx(
+  _env: Env,
+  code: String,
+  options: Option<TransformNodiffOptions>,
+) -> napi::Result<AsyncTask<TransformTask>> {
+  Ok(AsyncTask::new(TransformTask {
+    code,
+    options: options.unwrap_or_default(),
+  }))
+}
+
+#[napi]
+pub fn transform_react_lynx_dual_sync(
+  _env: Env,
+  code: String,
+  options: Option<TransformNodiffOptions>,
+) -> napi::Result<TransformNodiffDualOutput> {
+  let out = transform_react_lynx_dual_inner(&Globals::new(), code, options.unwrap_or_default());
+  napi::Result::Ok(out)
+}
+
+#[napi]
+impl Task for DualTransformTask {
+  type Output = TransformNodiffDualOutput;
+  type JsValue = TransformNodiffDualOutput;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let out =
+      transform_react_lynx_dual_inner(&Globals::new(), self.code.clone(), self.options.clone());
+    Ok(out)
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+}
+
+#[napi]
+pub fn transform_react_lynx_dual(
+  _env: Env,
+  code: String,
+  options: Option<TransformNodiffOptions>,
+) -> napi::Result<AsyncTask<DualTransformTask>> {
+  Ok(AsyncTask::new(DualTransformTask {
+    code,
+    options: options.unwrap_or_default(),
+  }))
+}
+
+#[napi]
+impl Task for BatchTransformTask {
+  type Output = Vec<TransformNodiffOutput>;
+  type JsValue = Vec<TransformNodiffOutput>;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let out = transform_react_lynx_batch_inner(std::mem::take(&mut self.items));
+    Ok(out)
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+}
+
+#[napi]
+pub fn transform_react_lynx_batch_sync(
+  _env: Env,
+  items: Vec<TransformBatchItem>,
+) -> napi::Result<Vec<TransformNodiffOutput>> {
+  let out = transform_react_lynx_batch_inner(items);
+  napi::Result::Ok(out)
+}
+
+#[napi]
+pub fn transform_react_lynx_batch(
+  _env: Env,
+  items: Vec<TransformBatchItem>,
+) -> napi::Result<AsyncTask<BatchTransformTask>> {
+  Ok(AsyncTask::new(BatchTransformTask { items }))
+}
+
+pub struct BundleTransformTask {
+  pub code: String,
+  pub options: bundle::TransformNodiffBundleOptions,
+}
+
+#[napi]
+impl Task for BundleTransformTask {
+  type Output = bundle::TransformNodiffBundleOutput;
+  type JsValue = bundle::TransformNodiffBundleOutput;
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let out = bundle::transform_bundle_result_inner(self.code.clone(), self.options.clone());
+    Ok(out)
+  }
+  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    napi::Result::Ok(output)
+  }
+}
+
+#[napi]
+pub fn transform_bundle_result_sync(
+  _env: Env,
+  code: String,
+  options: Option<bundle::TransformNodiffBundleOptions>,
+) -> napi::Result<bundle::TransformNodiffBundleOutput> {
+  let out = bundle::transform_bundle_result_inner(code, options.unwrap_or_default());
+  napi::Result::Ok(out)
+}
+
+#[napi]
+pub fn transform_bundle_result(
+  _env: Env,
+  code: String,
+  options: Option<bundle::TransformNodiffBundleOptions>,
+) -> napi::Result<AsyncTask<BundleTransformTask>> {
+  Ok(AsyncTask::new(BundleTransformTask {
+    code,
+    options: options.unwrap_or_default(),
+  }))
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod wasm {
+  use napi::{JsObject, NapiValue};
+
+  #[no_mangle]
+  pub unsafe extern "C" fn napi_register_wasm_v1(
+    raw_env: napi::sys::napi_env,
+    raw_exports: napi::sys::napi_value,
+  ) {
+    // let env = Env::from_raw(raw_env);
+    let mut exports = JsObject::from_raw_unchecked(raw_env, raw_exports);
+
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    {
+      let _ = exports.create_named_method("transformReactLynxSync", super::__napi__transform_react_lynx_sync);
+      let _ = exports.create_named_method("transformBundleResultSync", super::__napi__transform_bundle_result_sync);
+      let _ = exports.create_named_method("transformReactLynx", super::__napi__transform_react_lynx);
+      let _ = exports.create_named_method("transformBundleResult", super::__napi__transform_bundle_result);
+      let _ = exports.create_named_method("transformReactLynxBatchSync", super::__napi__transform_react_lynx_batch_sync);
+      let _ = exports.create_named_method("transformReactLynxBatch", super::__napi__transform_react_lynx_batch);
+      let _ = exports.create_named_method("transformReactLynxDualSync", super::__napi__transform_react_lynx_dual_sync);
+      let _ = exports.create_named_method("transformReactLynxDual", super::__napi__transform_react_lynx_dual);
+    }
+  }
+}
@@ -0,0 +1,69 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::Value;
+
+/// An option that is either a `boolean` or a value, `boolean | T` in JS.
+///
+/// Most of the passes use `BoolOr::Bool(false)` to disable the pass and
+/// `BoolOr::Value(config)` to enable it with the given config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoolOr<T> {
+  Bool(bool),
+  Value(T),
+}
+
+impl<T> From<bool> for BoolOr<T> {
+  fn from(value: bool) -> Self {
+    BoolOr::Bool(value)
+  }
+}
+
+impl<'de, T> Deserialize<'de> for BoolOr<T>
+where
+  T: DeserializeOwned,
+{
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    match Value::deserialize(deserializer)? {
+      Value::Bool(v) => Ok(BoolOr::Bool(v)),
+      v => T::deserialize(v)
+        .map(BoolOr::Value)
+        .map_err(serde::de::Error::custom),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
+impl<T> napi::bindgen_prelude::FromNapiValue for BoolOr<T>
+where
+  T: napi::bindgen_prelude::FromNapiValue
+    + napi::bindgen_prelude::TypeName
+    + napi::bindgen_prelude::ValidateNapiValue,
+{
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    match napi::Either::<bool, T>::from_napi_value(env, napi_val)? {
+      napi::Either::A(v) => Ok(BoolOr::Bool(v)),
+      napi::Either::B(v) => Ok(BoolOr::Value(v)),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
+impl<T> napi::bindgen_prelude::ToNapiValue for BoolOr<T>
+where
+  T: napi::bindgen_prelude::ToNapiValue,
+{
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    match val {
+      BoolOr::Bool(v) => <bool>::to_napi_value(env, v),
+      BoolOr::Value(v) => T::to_napi_value(env, v),
+    }
+  }
+}
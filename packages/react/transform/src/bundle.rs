@@ -1,7 +1,11 @@
 #![deny(clippy::all)]
 
+use crate::diagnostics::{ids, lint};
 use crate::esbuild::{EsbuildEmitter, PartialMessage};
-use napi::Either;
+use crate::panic_guard::panic_guard;
+use crate::utils::get_input_source_map;
+use crate::BoolOr;
+use serde::Deserialize;
 use std::vec;
 use swc_core::common::pass::Optional;
 use swc_core::{
@@ -27,17 +31,32 @@ use swc_core::{
 // currently `use xxx as yyy` is not supported by napi-rs
 // So we have to use different name
 use crate::swc_plugin_extract_str::{ExtractStrConfig, ExtractStrVisitor};
+use crate::swc_plugin_snapshot_dedupe::SnapshotDedupeVisitor;
 use crate::swc_plugin_worklet_post_process::WorkletPostProcessorVisitor;
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct TransformNodiffBundleOptions {
   pub filename: String,
   pub plugin_name: String,
   pub source_file_name: Option<String>,
-  pub sourcemap: Either<bool, String>,
-  pub extract_str: Either<bool, ExtractStrConfig>,
+  #[napi(ts_type = "boolean | string")]
+  pub sourcemap: BoolOr<String>,
+  /// @public
+  /// The source map of the input code, generated by the previous loaders.
+  /// Use `true` to read the inline `//# sourceMappingURL=data:...` of the input code.
+  #[napi(ts_type = "boolean | string")]
+  pub input_source_map: Option<BoolOr<String>>,
+  #[napi(ts_type = "boolean | ExtractStrConfig")]
+  pub extract_str: BoolOr<ExtractStrConfig>,
   pub minify: Option<bool>,
+  /// @public
+  /// Merge the snapshots with the same uid in one scope, see `JsxTransformerConfig.fingerprintUid`.
+  /// Only the modules concatenated into one scope are merged, e.g.: by
+  /// `optimization.concatenateModules`, the modules wrapped in their own factory functions are left
+  /// as they are.
+  pub dedupe_snapshots: Option<bool>,
 }
 
 impl Default for TransformNodiffBundleOptions {
@@ -46,9 +65,11 @@ impl Default for TransformNodiffBundleOptions {
       plugin_name: Default::default(),
       filename: Default::default(),
       source_file_name: Default::default(),
-      sourcemap: Either::A(false),
-      extract_str: Either::A(false),
+      sourcemap: BoolOr::Bool(false),
+      input_source_map: None,
+      extract_str: BoolOr::Bool(false),
       minify: Some(false),
+      dedupe_snapshots: None,
     }
   }
 }
@@ -101,6 +122,23 @@ pub fn transform_bundle_result_inner(
   let emitter = Box::new(MultiEmitter::new(vec![esbuild_emitter]));
   let handler = Handler::with_emitter(true, false, emitter);
 
+  let orig = match get_input_source_map(
+    options
+      .input_source_map
+      .as_ref()
+      .unwrap_or(&BoolOr::Bool(false)),
+    &fm.src,
+  ) {
+    Ok(orig) => orig,
+    Err(err) => {
+      handler
+        .struct_warn(format!("Failed to read input source map: {}", err).as_str())
+        .code(lint(ids::INPUT_SOURCE_MAP))
+        .emit();
+      None
+    }
+  };
+
   let result = GLOBALS.set(&Default::default(), || {
     let program = c.parse_js(
       fm,
@@ -111,8 +149,8 @@ pub fn transform_bundle_result_inner(
       Some(&comments),
     );
     let should_extract_str = match options.extract_str {
-      Either::A(config) => config,
-      Either::B(_) => true,
+      BoolOr::Bool(config) => config,
+      BoolOr::Value(_) => true,
     };
     let program = match program {
       Ok(program) => program,
@@ -132,19 +170,24 @@ pub fn transform_bundle_result_inner(
     };
 
     let mut extract_str_vis: ExtractStrVisitor = match options.extract_str {
-      Either::A(_) => ExtractStrVisitor::default(),
-      Either::B(config) => ExtractStrVisitor::new(config),
+      BoolOr::Bool(_) => ExtractStrVisitor::default(),
+      BoolOr::Value(config) => ExtractStrVisitor::new(config),
     };
     let extract_str_plugin =
       Optional::new(visit_mut_pass(&mut extract_str_vis), should_extract_str);
+    let snapshot_dedupe_plugin = Optional::new(
+      visit_mut_pass(SnapshotDedupeVisitor::default()),
+      matches!(options.dedupe_snapshots, Some(true)),
+    );
     let mut worklet_post_process_vis = WorkletPostProcessorVisitor::default();
     let worklet_post_process_plugin = visit_mut_pass(&mut worklet_post_process_vis);
 
     let pass = (
-      resolver(Mark::new(), Mark::new(), true),
-      extract_str_plugin,
-      worklet_post_process_plugin,
-      hygiene_with_config(Default::default()),
+      panic_guard("resolver", resolver(Mark::new(), Mark::new(), true)),
+      panic_guard("extractStr", extract_str_plugin),
+      panic_guard("dedupeSnapshots", snapshot_dedupe_plugin),
+      panic_guard("workletPostProcess", worklet_post_process_plugin),
+      panic_guard("hygiene", hygiene_with_config(Default::default())),
     );
 
     let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
@@ -161,11 +204,11 @@ pub fn transform_bundle_result_inner(
         output_path: None,
         inline_sources_content: true,
         source_map: match options.sourcemap {
-          Either::A(b) => SourceMapsConfig::Bool(b),
-          Either::B(s) => SourceMapsConfig::Str(s),
+          BoolOr::Bool(b) => SourceMapsConfig::Bool(b),
+          BoolOr::Value(s) => SourceMapsConfig::Str(s),
         },
         source_map_names: &Default::default(),
-        orig: None,
+        orig: orig.as_ref(),
         comments: Some(&comments),
         emit_source_map_columns: true,
         preamble: "".into(),
@@ -234,7 +277,7 @@ pub fn transform_bundle_result_inner(
 //                 plugin_name: Default::default(),
 //                 filename: Default::default(),
 //                 source_file_name: Default::default(),
-//                 sourcemap: Either::A(false),
+//                 sourcemap: BoolOr::Bool(false),
 //                 extract_str: ExtractStrConfig {
 //                     str_length: 1,
 //                     extracted_str_arr: Some(vec!["c230e9ac4259909e9e83419cbd2a9960".to_string()]),
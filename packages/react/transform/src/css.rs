@@ -1,17 +1,13 @@
 use convert_case::{Case, Casing};
 use swc_core::{
-  common::{
-    errors::{DiagnosticId, HANDLER},
-    Span,
-  },
+  common::{errors::HANDLER, Span},
   ecma::{self, ast::Expr, utils::is_literal},
 };
 
 use crate::css_property::CSS_PROPERTY_MAP;
+use crate::diagnostics::{ids, lint};
 use crate::utils::jsonify;
 
-static EXTRACT_CSS_DIAGNOSTIC_ID: &str = "react-lynx-extract-css";
-
 /// `get_inline_style_from_object` extract CSSPropertyID and CSSValue from a [`ObjectLit`](ecma::ast::ObjectLit).
 /// An empty Vec will be returned if the extraction failed.
 ///
@@ -64,7 +60,7 @@ pub fn get_inline_style_from_object(object: &ecma::ast::ObjectLit) -> Vec<(u32,
                 .struct_span_warn_with_code(
                   span,
                   "Unknown css property, fallbak to SetInlineStyle",
-                  DiagnosticId::Lint(EXTRACT_CSS_DIAGNOSTIC_ID.into()),
+                  lint(ids::EXTRACT_CSS),
                 )
                 .emit();
               Err(())
@@ -118,7 +114,7 @@ pub fn get_string_inline_style_from_literal(expr: &Expr, span: &Span) -> Option<
             .struct_span_warn_with_code(
               *span,
               "Unexpected literal for style",
-              DiagnosticId::Lint(EXTRACT_CSS_DIAGNOSTIC_ID.into()),
+              lint(ids::EXTRACT_CSS),
             )
             .emit();
         });
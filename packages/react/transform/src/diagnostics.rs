@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use swc_core::common::errors::{DiagnosticBuilder, DiagnosticId, Emitter, Level};
+
+/// The ids of the diagnostics, which can be used as the keys of
+/// `TransformNodiffOptions::diagnostics`.
+pub mod ids {
+  /// The inline style can not be extracted, e.g.: unknown css property.
+  pub const EXTRACT_CSS: &str = "react-lynx-extract-css";
+  /// The input source map of the previous loaders is invalid.
+  pub const INPUT_SOURCE_MAP: &str = "react-lynx-input-source-map";
+  /// `key` is used on a non-root element of a snapshot.
+  pub const KEY_NOT_ON_ROOT: &str = "react-lynx-key-not-on-root";
+  /// A directive inside a class constructor.
+  pub const DIRECTIVE_IN_CONSTRUCTOR: &str = "react-lynx-directive-in-constructor";
+  /// A directive inside a getter or setter.
+  pub const DIRECTIVE_IN_ACCESSOR: &str = "react-lynx-directive-in-accessor";
+  /// A creation attribute of a custom element in `JSXTransformerConfig::elements` is not static.
+  pub const ELEMENT_CREATION_ATTRIBUTE: &str = "react-lynx-element-creation-attribute";
+  /// The children of a custom element in `JSXTransformerConfig::elements` are not allowed.
+  pub const ELEMENT_CHILDREN: &str = "react-lynx-element-children";
+  /// A per-file pragma has an invalid value, e.g.: `@jsxCSSId foo`.
+  pub const INVALID_PRAGMA: &str = "react-lynx-invalid-pragma";
+  /// A main thread function captures a value which can not be sent to the main thread, e.g.: a
+  /// class instance or a background function.
+  pub const UNSERIALIZABLE_CAPTURE: &str = "react-lynx-unserializable-capture";
+  /// A main thread function is a generator, which the worklet runtime can not resume.
+  pub const GENERATOR_WORKLET: &str = "react-lynx-generator-worklet";
+  /// The Promise of `runOnBackground(fn)()` is used as the result of `fn`.
+  pub const UNAWAITED_RUN_ON_BACKGROUND: &str = "react-lynx-unawaited-run-on-background";
+  /// A custom worklet directive of `WorkletVisitorConfig::directives` is invalid, e.g.: its
+  /// `register` is not an identifier.
+  pub const INVALID_WORKLET_DIRECTIVE: &str = "react-lynx-invalid-worklet-directive";
+
+  /// `DEPRECATED:` the components package is removed.
+  pub const DEPRECATED_COMPONENTS_PKG: &str = "react-lynx-deprecated-components-pkg";
+  /// `DEPRECATED:` the old runtime package is renamed.
+  pub const DEPRECATED_RUNTIME_PKG: &str = "react-lynx-deprecated-runtime-pkg";
+  /// `DEPRECATED:` `<component is=? />`.
+  pub const DEPRECATED_COMPONENT_IS: &str = "react-lynx-deprecated-component-is";
+  /// `DEPRECATED:` the old event props, e.g.: `bindtap` on components.
+  pub const DEPRECATED_EVENT_PROPS: &str = "react-lynx-deprecated-event-props";
+  /// `DEPRECATED:` `lynx-key`.
+  pub const DEPRECATED_LYNX_KEY: &str = "react-lynx-deprecated-lynx-key";
+  /// `DEPRECATED:` the old element names.
+  pub const DEPRECATED_ELEMENT_NAME: &str = "react-lynx-deprecated-element-name";
+
+  /// `addComponentElement` skips the components with JSX spread.
+  pub const COMPONENT_ELEMENT_SPREAD: &str = "react-lynx-component-element-spread";
+  /// `BROKEN:` `item-key` in component props.
+  pub const BROKEN_ITEM_KEY: &str = "react-lynx-broken-item-key";
+  /// `BROKEN:` `e.stopPropagation()`.
+  pub const BROKEN_STOP_PROPAGATION: &str = "react-lynx-broken-stop-propagation";
+  /// `BROKEN:` the selector methods on component instances, e.g.: `this.getNodeRef`.
+  pub const BROKEN_INSTANCE_SELECTOR: &str = "react-lynx-broken-instance-selector";
+  /// `BROKEN:` the class property `config`.
+  pub const BROKEN_CLASS_CONFIG: &str = "react-lynx-broken-class-config";
+}
+
+/// A `DiagnosticId` of `id`, to be used with `struct_span_warn_with_code`.
+pub fn lint(id: &str) -> DiagnosticId {
+  DiagnosticId::Lint(id.into())
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+  /// Drop the diagnostic.
+  Off,
+  /// Report the diagnostic as a warning.
+  Warn,
+  /// Report the diagnostic as an error.
+  Error,
+}
+
+#[cfg(feature = "napi")]
+impl napi::bindgen_prelude::FromNapiValue for DiagnosticSeverity {
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    let val = <&str>::from_napi_value(env, napi_val).map_err(|e| {
+      napi::bindgen_prelude::error!(
+        e.status,
+        "Failed to convert napi value into enum `{}`. {}",
+        "DiagnosticSeverity",
+        e,
+      )
+    })?;
+    match val {
+      "off" => Ok(DiagnosticSeverity::Off),
+      "warn" => Ok(DiagnosticSeverity::Warn),
+      "error" => Ok(DiagnosticSeverity::Error),
+      _ => Err(napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "value `{}` does not match any variant of enum `{}`",
+        val,
+        "DiagnosticSeverity"
+      )),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
+impl napi::bindgen_prelude::ToNapiValue for DiagnosticSeverity {
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    let val = match val {
+      DiagnosticSeverity::Off => "off",
+      DiagnosticSeverity::Warn => "warn",
+      DiagnosticSeverity::Error => "error",
+    };
+    <&str>::to_napi_value(env, val)
+  }
+}
+
+/// An emitter that overrides the level of the diagnostics before forwarding them to `emitter`.
+///
+/// The severity in `severities` of the diagnostic id takes precedence, then
+/// `all_warnings_as_errors` turns the remaining warnings into errors.
+pub struct SeverityEmitter {
+  emitter: Box<dyn Emitter>,
+  severities: HashMap<String, DiagnosticSeverity>,
+  all_warnings_as_errors: bool,
+}
+
+impl SeverityEmitter {
+  pub fn new(
+    emitter: Box<dyn Emitter>,
+    severities: HashMap<String, DiagnosticSeverity>,
+    all_warnings_as_errors: bool,
+  ) -> Self {
+    Self {
+      emitter,
+      severities,
+      all_warnings_as_errors,
+    }
+  }
+}
+
+impl Emitter for SeverityEmitter {
+  fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+    let severity = db.code.as_ref().and_then(|code| match code {
+      DiagnosticId::Error(id) | DiagnosticId::Lint(id) => self.severities.get(id.as_str()),
+    });
+
+    match severity {
+      Some(DiagnosticSeverity::Off) => return,
+      Some(DiagnosticSeverity::Warn) => db.level = Level::Warning,
+      Some(DiagnosticSeverity::Error) => db.level = Level::Error,
+      None if self.all_warnings_as_errors && db.level == Level::Warning => db.level = Level::Error,
+      None => {}
+    }
+
+    self.emitter.emit(db);
+  }
+}
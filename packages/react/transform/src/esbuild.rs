@@ -1,9 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::sync::RwLock;
 use swc_core::common::{
-  errors::{DiagnosticBuilder, DiagnosticId, Emitter, Level},
+  errors::{Applicability, DiagnosticBuilder, DiagnosticId, Emitter, Level, SubstitutionPart},
   sync::Lrc,
-  FileLines, SourceMapperDyn,
+  FileLines, SourceMapperDyn, Span,
 };
 
 /// This is esbuild's PartialMessage definition.
@@ -41,6 +41,29 @@ pub struct PartialLocation {
   pub suggestion: Option<String>,
 }
 
+/// A fix of a diagnostic that can be applied without human review.
+#[napi(object)]
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Fix {
+  /// The id of the diagnostic, e.g.: `react-lynx-deprecated-lynx-key`.
+  pub id: Option<String>,
+  pub text: String,
+  pub edits: Vec<TextEdit>,
+}
+
+/// Replace `start..end` of `file` with `replacement`.
+/// The range is in UTF-8 byte offsets of the input code.
+#[napi(object)]
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+  pub file: String,
+  pub start: u32,
+  pub end: u32,
+  pub replacement: String,
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -85,6 +108,73 @@ mod tests {
     assert_eq!(s.detail, Some("test".to_string()));
   }
 
+  #[test]
+  fn test_emit_notes_and_fixes() {
+    use swc_core::common::{
+      errors::{Applicability, Handler},
+      BytePos, FileName, SourceMap, Span,
+    };
+
+    let cm = Lrc::new(SourceMap::default());
+    let fm = cm.new_source_file(
+      FileName::Real("index.jsx".into()).into(),
+      "<view lynx-key={1} />".into(),
+    );
+    let span = |lo: u32, hi: u32| Span::new(fm.start_pos + BytePos(lo), fm.start_pos + BytePos(hi));
+
+    let (emitter, _, warnings) = EsbuildEmitter::new("test".into(), Some(cm.clone()));
+    let fixes = emitter.fixes.clone();
+    let handler = Handler::with_emitter(true, false, Box::new(emitter));
+
+    handler
+      .struct_span_warn(span(6, 14), "lynx-key is changed to key")
+      .span_note(span(1, 5), "on this element")
+      .span_suggestion_with_applicability(
+        span(6, 14),
+        "use `key`",
+        "key".into(),
+        Applicability::MachineApplicable,
+      )
+      .span_suggestion_with_applicability(
+        span(15, 18),
+        "maybe a string",
+        "\"1\"".into(),
+        Applicability::MaybeIncorrect,
+      )
+      .emit();
+
+    let warnings = warnings.read().unwrap();
+    let location = warnings[0].location.as_ref().unwrap();
+    assert_eq!(location.suggestion.as_deref(), Some("key"));
+
+    let notes = warnings[0].notes.as_ref().unwrap();
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[0].text.as_deref(), Some("on this element"));
+    assert_eq!(notes[0].location.as_ref().unwrap().column, Some(1));
+    assert_eq!(notes[1].text.as_deref(), Some("maybe a string"));
+    assert_eq!(
+      notes[1].location.as_ref().unwrap().suggestion.as_deref(),
+      Some("\"1\"")
+    );
+
+    // Only the machine-applicable suggestion is a fix.
+    let fixes = fixes.read().unwrap();
+    assert_eq!(
+      *fixes,
+      vec![Fix {
+        id: None,
+        text: "use `key`".into(),
+        edits: vec![TextEdit {
+          file: "index.jsx".into(),
+          start: 6,
+          end: 14,
+          replacement: "key".into(),
+        }],
+      }]
+    );
+    assert_eq!(apply_fixes(&fm.src, &fixes), "<view key={1} />");
+  }
+
   #[test]
   fn test_partial_location() {
     let s = PartialLocation {
@@ -111,9 +201,35 @@ mod tests {
   }
 }
 
+/// Apply the `fixes` to `code`, the edits overlapping with a previous one are skipped.
+pub fn apply_fixes(code: &str, fixes: &[Fix]) -> String {
+  let mut edits = fixes
+    .iter()
+    .flat_map(|fix| &fix.edits)
+    .filter(|edit| edit.start <= edit.end && edit.end as usize <= code.len())
+    .collect::<Vec<_>>();
+  edits.sort_by_key(|edit| (edit.start, edit.end));
+
+  let mut output = String::with_capacity(code.len());
+  let mut last = 0;
+  for edit in edits {
+    let (start, end) = (edit.start as usize, edit.end as usize);
+    if start < last || !code.is_char_boundary(start) || !code.is_char_boundary(end) {
+      continue;
+    }
+    output.push_str(&code[last..start]);
+    output.push_str(&edit.replacement);
+    last = end;
+  }
+  output.push_str(&code[last..]);
+  output
+}
+
 pub struct EsbuildEmitter {
   pub errors: Lrc<RwLock<Vec<PartialMessage>>>,
   pub warnings: Lrc<RwLock<Vec<PartialMessage>>>,
+  /// The machine-applicable suggestions of the emitted diagnostics.
+  pub fixes: Lrc<RwLock<Vec<Fix>>>,
 
   plugin_name: String,
   source_map: Option<Lrc<SourceMapperDyn>>,
@@ -135,6 +251,7 @@ impl EsbuildEmitter {
       Self {
         errors: errors.clone(),
         warnings: warnings.clone(),
+        fixes: Default::default(),
 
         plugin_name,
         source_map,
@@ -143,51 +260,123 @@ impl EsbuildEmitter {
       warnings,
     )
   }
+
+  fn location(&self, span: Span) -> Option<PartialLocation> {
+    let sm = self.source_map.as_ref()?;
+    if span.is_dummy() {
+      return None;
+    }
+
+    let loc = sm.lookup_char_pos(span.lo());
+    let filename = sm.span_to_filename(span);
+
+    let mut location = PartialLocation {
+      file: Some(filename.to_string()),
+      namespace: None,
+      line: Some(loc.line as u32),
+      column: Some(loc.col.0 as u32),
+      length: Some(span.hi().0 - span.lo().0),
+      line_text: None,
+      suggestion: None,
+    };
+
+    match sm.span_to_lines(span) {
+      Ok(FileLines { lines, file }) => {
+        lines.iter().for_each(|line| {
+          if line.line_index + 1 == loc.line {
+            if let Some(line_text) = file.get_line(line.line_index) {
+              location.line_text = Some(line_text.to_string());
+            }
+          }
+        });
+      }
+      Err(_) => {}
+    }
+
+    Some(location)
+  }
+
+  fn text_edit(&self, part: &SubstitutionPart) -> Option<TextEdit> {
+    let sm = self.source_map.as_ref()?;
+    if part.span.is_dummy() {
+      return None;
+    }
+
+    let file = sm.lookup_char_pos(part.span.lo()).file;
+    Some(TextEdit {
+      file: sm.span_to_filename(part.span).to_string(),
+      start: (part.span.lo() - file.start_pos).0,
+      end: (part.span.hi() - file.start_pos).0,
+      replacement: part.snippet.clone(),
+    })
+  }
 }
 
 impl Emitter for EsbuildEmitter {
   fn emit(&mut self, msg: &mut DiagnosticBuilder<'_>) {
+    let id = msg.code.as_ref().map(|code| match code {
+      DiagnosticId::Error(id) => id.to_string(),
+      DiagnosticId::Lint(id) => id.to_string(),
+    });
+    let primary_span = msg.span.primary_span();
+    let mut location = primary_span.and_then(|span| self.location(span));
+
+    let mut notes = msg
+      .children
+      .iter()
+      .map(|child| PartialNote {
+        text: Some(child.message()),
+        location: child
+          .span
+          .primary_span()
+          .and_then(|span| self.location(span)),
+      })
+      .collect::<Vec<_>>();
+
+    for suggestion in &msg.suggestions {
+      // Only the first substitution is used, the others are alternatives.
+      let Some(substitution) = suggestion.substitutions.first() else {
+        continue;
+      };
+
+      match (substitution.parts.as_slice(), location.as_mut()) {
+        // The suggestion of the primary span is shown inline by esbuild.
+        ([part], Some(location))
+          if Some(part.span) == primary_span && location.suggestion.is_none() =>
+        {
+          location.suggestion = Some(part.snippet.clone());
+        }
+        (parts, _) => notes.extend(parts.iter().map(|part| PartialNote {
+          text: Some(suggestion.msg.clone()),
+          location: self.location(part.span).map(|location| PartialLocation {
+            suggestion: Some(part.snippet.clone()),
+            ..location
+          }),
+        })),
+      }
+
+      if suggestion.applicability == Applicability::MachineApplicable {
+        let edits = substitution
+          .parts
+          .iter()
+          .filter_map(|part| self.text_edit(part))
+          .collect::<Vec<_>>();
+        if !edits.is_empty() {
+          self.fixes.write().unwrap().push(Fix {
+            id: id.clone(),
+            text: suggestion.msg.clone(),
+            edits,
+          });
+        }
+      }
+    }
+
     let partial_message = PartialMessage {
-      id: msg.code.as_ref().map(|code| match code {
-        DiagnosticId::Error(id) => id.to_string(),
-        DiagnosticId::Lint(id) => id.to_string(),
-      }),
+      id,
       plugin_name: Some(self.plugin_name.clone()),
       text: Some(msg.message().to_string()),
-      location: if let (Some(sm), Some(primary_span)) =
-        (self.source_map.as_ref(), msg.span.primary_span().as_ref())
-      {
-        let loc = sm.lookup_char_pos(primary_span.lo());
-        let filename = sm.span_to_filename(*primary_span);
-
-        let mut location = PartialLocation {
-          file: Some(filename.to_string()),
-          namespace: None,
-          line: Some(loc.line as u32),
-          column: Some(loc.col.0 as u32),
-          length: Some(primary_span.hi().0 - primary_span.lo().0),
-          line_text: None,
-          suggestion: None,
-        };
-
-        match sm.span_to_lines(*primary_span) {
-          Ok(FileLines { lines, file }) => {
-            lines.iter().for_each(|line| {
-              if line.line_index + 1 == loc.line {
-                if let Some(line_text) = file.get_line(line.line_index) {
-                  location.line_text = Some(line_text.to_string());
-                }
-              }
-            });
-          }
-          Err(_) => {}
-        }
-
-        Some(location)
-      } else {
-        None
-      },
-      notes: None,
+      location,
+      notes: if notes.is_empty() { None } else { Some(notes) },
       detail: None,
     };
 
@@ -195,12 +384,10 @@ impl Emitter for EsbuildEmitter {
       Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => {
         self.errors.write().unwrap().push(partial_message);
       }
-      Level::Warning => {
+      Level::Warning | Level::Note | Level::Help | Level::FailureNote => {
         self.warnings.write().unwrap().push(partial_message);
       }
-      _ => {
-        todo!()
-      }
+      Level::Cancelled => {}
     }
   }
 }
@@ -1,11 +1,24 @@
 #![deny(clippy::all)]
 
+#[cfg(feature = "napi")]
 #[macro_use]
 extern crate napi_derive;
+// Strip the `#[napi]` attributes of the config structs when the napi binding is not built.
+#[cfg(not(feature = "napi"))]
+#[macro_use]
+extern crate react_transform_napi_shim;
+// The napi functions are only registered in the library, they are unused in the tests.
+#[cfg(all(feature = "napi", not(test)))]
+mod binding;
+mod bool_or;
 mod bundle;
 mod css;
 mod css_property;
+mod diagnostics;
 mod esbuild;
+mod panic_guard;
+mod pragma;
+mod swc_plugin;
 mod swc_plugin_compat;
 mod swc_plugin_compat_post;
 mod swc_plugin_css_scope;
@@ -17,16 +30,31 @@ mod swc_plugin_inject;
 mod swc_plugin_refresh;
 mod swc_plugin_shake;
 mod swc_plugin_snapshot;
+mod swc_plugin_snapshot_dedupe;
 mod swc_plugin_worklet;
 mod swc_plugin_worklet_post_process;
 mod target;
+mod transformer;
 mod utils;
 
-use std::vec;
-
-use napi::{bindgen_prelude::AsyncTask, Either, Env, Task};
+pub use bool_or::BoolOr;
+pub use bundle::{
+  transform_bundle_result_inner, TransformNodiffBundleOptions, TransformNodiffBundleOutput,
+};
+pub use diagnostics::{ids as diagnostic_ids, DiagnosticSeverity};
+pub use esbuild::{apply_fixes, Fix, PartialLocation, PartialMessage, PartialNote, TextEdit};
+
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  iter,
+  rc::Rc,
+  sync::{Mutex, RwLock},
+  vec,
+};
 
 use rustc_hash::FxBuildHasher;
+use serde::Deserialize;
 
 use swc_core::{
   atoms::Atom,
@@ -39,7 +67,7 @@ use swc_core::{
     errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
     pass::Optional,
     sync::Lrc,
-    FileName, FilePathMapping, Mark, SourceMap, GLOBALS,
+    FileName, FilePathMapping, Globals, Mark, SourceMap, Spanned, GLOBALS,
   },
   ecma::{
     ast::*,
@@ -61,20 +89,38 @@ use swc_core::{
 
 // currently `use xxx as yyy` is not supported by napi-rs
 // So we have to use different name
-use swc_plugin_compat::{CompatVisitor, CompatVisitorConfig};
+use diagnostics::{ids, lint, SeverityEmitter};
+use panic_guard::panic_guard;
+use pragma::eliminate_module;
+pub use pragma::{JsxRuntime, Pragmas};
+pub use swc_plugin::{swc_plugin_pass, SwcPluginConfig};
+pub use swc_plugin_compat::{
+  AddComponentElementConfig, CompatVisitor, CompatVisitorConfig, DarkModeConfig,
+};
 use swc_plugin_compat_post::CompatPostVisitor;
-use swc_plugin_css_scope::{CSSScopeVisitor, CSSScopeVisitorConfig};
-use swc_plugin_define_dce::DefineDCEVisitorConfig;
-use swc_plugin_directive_dce::{DirectiveDCEVisitor, DirectiveDCEVisitorConfig};
-use swc_plugin_dynamic_import::{DynamicImportVisitor, DynamicImportVisitorConfig};
-use swc_plugin_inject::{InjectVisitor, InjectVisitorConfig};
-use swc_plugin_refresh::{RefreshVisitor, RefreshVisitorConfig};
-use swc_plugin_shake::{ShakeVisitor, ShakeVisitorConfig};
-use swc_plugin_snapshot::{JSXTransformer, JSXTransformerConfig};
-use swc_plugin_worklet::{WorkletVisitor, WorkletVisitorConfig};
-use utils::calc_hash;
-
-#[derive(Debug, PartialEq, Clone, Copy)]
+pub use swc_plugin_css_scope::{CSSScope, CSSScopeVisitor, CSSScopeVisitorConfig};
+pub use swc_plugin_define_dce::DefineDCEVisitorConfig;
+pub use swc_plugin_directive_dce::{DirectiveDCEVisitor, DirectiveDCEVisitorConfig};
+pub use swc_plugin_dynamic_import::{
+  DynamicImportManifest, DynamicImportVisitor, DynamicImportVisitorConfig,
+};
+pub use swc_plugin_extract_str::ExtractStrConfig;
+pub use swc_plugin_inject::{InjectAs, InjectVisitor, InjectVisitorConfig};
+pub use swc_plugin_refresh::{RefreshVisitor, RefreshVisitorConfig};
+pub use swc_plugin_shake::{ShakeVisitor, ShakeVisitorConfig};
+pub use swc_plugin_snapshot::{
+  ComponentConfig, DynamicPartManifest, ElementChildren, ElementConfig, JSXTransformer,
+  JSXTransformerConfig, SnapshotElementManifest, SnapshotManifest,
+};
+pub use swc_plugin_worklet::{
+  WorkletDirective, WorkletManifest, WorkletVisitor, WorkletVisitorConfig,
+};
+pub use target::TransformTarget;
+pub use transformer::Transformer;
+use utils::{calc_hash, get_input_source_map};
+
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TransformMode {
   /// Transform for production.
   Production,
@@ -84,6 +130,7 @@ pub enum TransformMode {
   Test,
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::FromNapiValue for TransformMode {
   unsafe fn from_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -111,6 +158,7 @@ impl napi::bindgen_prelude::FromNapiValue for TransformMode {
   }
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::ToNapiValue for TransformMode {
   unsafe fn to_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -145,6 +193,20 @@ impl Default for SyntaxConfig {
   }
 }
 
+impl<'de> Deserialize<'de> for SyntaxConfig {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    // Same as napi, the syntax config is a JSON string.
+    let val = String::deserialize(deserializer)?;
+    serde_json::from_str(&val)
+      .map(SyntaxConfig)
+      .map_err(|err| serde::de::Error::custom(format!("SyntaxConfig: {}", err)))
+  }
+}
+
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::FromNapiValue for SyntaxConfig {
   unsafe fn from_napi_value(
     env: napi::sys::napi_env,
@@ -162,6 +224,7 @@ impl napi::bindgen_prelude::FromNapiValue for SyntaxConfig {
   }
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::ToNapiValue for SyntaxConfig {
   unsafe fn to_napi_value(
     env: napi::sys::napi_env,
@@ -189,6 +252,22 @@ impl From<IsModuleConfig> for IsModule {
   }
 }
 
+impl<'de> Deserialize<'de> for IsModuleConfig {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    match serde_json::Value::deserialize(deserializer)? {
+      serde_json::Value::Bool(v) => Ok(IsModuleConfig(IsModule::Bool(v))),
+      serde_json::Value::String(v) if v == "unknown" => Ok(IsModuleConfig(IsModule::Unknown)),
+      _ => Err(serde::de::Error::custom(
+        "value does not match any variant of enum `IsModuleConfig`",
+      )),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::FromNapiValue for IsModuleConfig {
   unsafe fn from_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -215,6 +294,7 @@ impl napi::bindgen_prelude::FromNapiValue for IsModuleConfig {
   }
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::ToNapiValue for IsModuleConfig {
   unsafe fn to_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -228,37 +308,64 @@ impl napi::bindgen_prelude::ToNapiValue for IsModuleConfig {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct TransformNodiffOptions {
   /// @internal
   /// This is used internally to make sure the test output is consistent.
   #[napi(ts_type = "'production' | 'development' | 'test'")]
   pub mode: Option<TransformMode>,
   pub plugin_name: String,
+  /// @public
+  /// Override the severity of the diagnostics by id, e.g.: `{ "react-lynx-extract-css": "off" }`.
+  #[napi(ts_type = "Record<string, 'off' | 'warn' | 'error'>")]
+  pub diagnostics: Option<HashMap<String, DiagnosticSeverity>>,
+  /// @public
+  /// Report the warnings as errors, except the ones configured in `diagnostics`.
+  pub all_warnings_as_errors: Option<bool>,
   pub filename: String,
   pub source_file_name: Option<String>,
-  pub sourcemap: Either<bool, String>,
+  #[napi(ts_type = "boolean | string")]
+  pub sourcemap: BoolOr<String>,
   pub source_map_columns: Option<bool>,
   pub inline_sources_content: Option<bool>,
   /// @public
+  /// The source map of the input code, generated by the previous loaders.
+  /// Use `true` to read the inline `//# sourceMappingURL=data:...` of the input code.
+  #[napi(ts_type = "boolean | string")]
+  pub input_source_map: Option<BoolOr<String>>,
+  /// @public
   /// This is swc syntax config in JSON format
   #[napi(ts_type = "string")]
   pub syntax_config: Option<SyntaxConfig>,
   #[napi(ts_type = "boolean | 'unknown'")]
   pub is_module: Option<IsModuleConfig>,
-  pub css_scope: Either<bool, CSSScopeVisitorConfig>,
-  pub snapshot: Option<Either<bool, JSXTransformerConfig>>,
-  pub shake: Either<bool, ShakeVisitorConfig>,
-  pub compat: Either<bool, CompatVisitorConfig>,
-  pub refresh: Either<bool, RefreshVisitorConfig>,
-  #[napi(js_name = "defineDCE")]
-  pub define_dce: Either<bool, DefineDCEVisitorConfig>,
-  #[napi(js_name = "directiveDCE")]
-  pub directive_dce: Either<bool, DirectiveDCEVisitorConfig>,
-  pub worklet: Either<bool, WorkletVisitorConfig>,
-  pub dynamic_import: Option<Either<bool, DynamicImportVisitorConfig>>,
+  #[napi(ts_type = "boolean | CssScopeVisitorConfig")]
+  pub css_scope: BoolOr<CSSScopeVisitorConfig>,
+  #[napi(ts_type = "boolean | JsxTransformerConfig")]
+  pub snapshot: Option<BoolOr<JSXTransformerConfig>>,
+  #[napi(ts_type = "boolean | ShakeVisitorConfig")]
+  pub shake: BoolOr<ShakeVisitorConfig>,
+  #[napi(ts_type = "boolean | CompatVisitorConfig")]
+  pub compat: BoolOr<CompatVisitorConfig>,
+  #[napi(ts_type = "boolean | RefreshVisitorConfig")]
+  pub refresh: BoolOr<RefreshVisitorConfig>,
+  #[napi(js_name = "defineDCE", ts_type = "boolean | DefineDceVisitorConfig")]
+  #[serde(rename = "defineDCE")]
+  pub define_dce: BoolOr<DefineDCEVisitorConfig>,
+  #[napi(
+    js_name = "directiveDCE",
+    ts_type = "boolean | DirectiveDceVisitorConfig"
+  )]
+  #[serde(rename = "directiveDCE")]
+  pub directive_dce: BoolOr<DirectiveDCEVisitorConfig>,
+  #[napi(ts_type = "boolean | WorkletVisitorConfig")]
+  pub worklet: BoolOr<WorkletVisitorConfig>,
+  #[napi(ts_type = "boolean | DynamicImportVisitorConfig")]
+  pub dynamic_import: Option<BoolOr<DynamicImportVisitorConfig>>,
   /// @internal
-  pub inject: Option<Either<bool, InjectVisitorConfig>>,
+  #[napi(ts_type = "boolean | InjectVisitorConfig")]
+  pub inject: Option<BoolOr<InjectVisitorConfig>>,
 }
 
 impl Default for TransformNodiffOptions {
@@ -266,36 +373,129 @@ impl Default for TransformNodiffOptions {
     Self {
       mode: Some(TransformMode::Production),
       plugin_name: Default::default(),
+      diagnostics: None,
+      all_warnings_as_errors: None,
       filename: Default::default(),
       source_file_name: Default::default(),
-      sourcemap: Either::A(false),
+      sourcemap: BoolOr::Bool(false),
       source_map_columns: None,
       inline_sources_content: None,
+      input_source_map: None,
       syntax_config: None,
       is_module: Default::default(),
-      css_scope: Either::B(Default::default()),
+      css_scope: BoolOr::Value(Default::default()),
       snapshot: Default::default(),
-      shake: Either::A(false),
-      compat: Either::A(false),
-      refresh: Either::A(false),
-      define_dce: Either::A(false),
-      directive_dce: Either::A(false),
-      worklet: Either::A(false),
-      dynamic_import: Some(Either::B(Default::default())),
-      inject: Some(Either::A(false)),
+      shake: BoolOr::Bool(false),
+      compat: BoolOr::Bool(false),
+      refresh: BoolOr::Bool(false),
+      define_dce: BoolOr::Bool(false),
+      directive_dce: BoolOr::Bool(false),
+      worklet: BoolOr::Bool(false),
+      dynamic_import: Some(BoolOr::Value(Default::default())),
+      inject: Some(BoolOr::Bool(false)),
+    }
+  }
+}
+
+impl TransformNodiffOptions {
+  /// The options of `target` in a dual transform, see `transform_react_lynx_dual_inner`.
+  ///
+  /// The `target` of `snapshot`, `worklet`, `compat` and `directiveDCE` is replaced, and so are
+  /// the thread defines of `defineDCE`, e.g.: `__LEPUS__`. `shake` only runs on the main thread.
+  fn with_target(mut self, target: TransformTarget) -> Self {
+    fn retarget<T: Default>(config: BoolOr<T>, f: impl FnOnce(T) -> T) -> BoolOr<T> {
+      match config {
+        BoolOr::Bool(false) => BoolOr::Bool(false),
+        BoolOr::Bool(true) => BoolOr::Value(f(T::default())),
+        BoolOr::Value(config) => BoolOr::Value(f(config)),
+      }
+    }
+
+    let is_lepus = target == TransformTarget::LEPUS;
+
+    self.snapshot = Some(match self.snapshot.unwrap_or(BoolOr::Bool(true)) {
+      // Same as the default config in `transform_react_lynx_inner`.
+      BoolOr::Bool(true) => BoolOr::Value(JSXTransformerConfig {
+        filename: self.filename.clone(),
+        target,
+        ..Default::default()
+      }),
+      config => retarget(config, |config| JSXTransformerConfig { target, ..config }),
+    });
+    self.worklet = retarget(self.worklet, |config| WorkletVisitorConfig {
+      target,
+      ..config
+    });
+    self.compat = retarget(self.compat, |config| CompatVisitorConfig {
+      target,
+      ..config
+    });
+    self.directive_dce = retarget(self.directive_dce, |_| DirectiveDCEVisitorConfig { target });
+
+    if let BoolOr::Value(config) = &mut self.define_dce {
+      for (key, value) in [
+        ("__LEPUS__", is_lepus),
+        ("__MAIN_THREAD__", is_lepus),
+        ("__JS__", !is_lepus),
+        ("__BACKGROUND__", !is_lepus),
+      ] {
+        config.define.insert(key.into(), value.to_string());
+      }
+    }
+
+    if !is_lepus {
+      self.shake = BoolOr::Bool(false);
     }
+
+    self
+  }
+
+  /// The thread compiled by these options, `None` for `MIXED` and `SSR`.
+  fn thread_target(&self) -> Option<TransformTarget> {
+    let target = match &self.snapshot {
+      Some(BoolOr::Value(config)) => config.target,
+      Some(BoolOr::Bool(false)) => match &self.directive_dce {
+        BoolOr::Value(config) => config.target,
+        BoolOr::Bool(_) => TransformTarget::LEPUS,
+      },
+      // Same as the default config in `transform_react_lynx_inner`.
+      _ => TransformTarget::LEPUS,
+    };
+    matches!(target, TransformTarget::LEPUS | TransformTarget::JS).then_some(target)
   }
 }
 
+/// The structured description of what the transform generated for a module.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct TransformNodiffManifest {
+  pub snapshots: Vec<SnapshotManifest>,
+  pub worklets: Vec<WorkletManifest>,
+  pub dynamic_imports: Vec<DynamicImportManifest>,
+}
+
 #[napi(object)]
 pub struct TransformNodiffOutput {
   pub code: String,
   pub map: Option<String>,
+  /// `None` when the module failed to parse or print.
+  pub manifest: Option<TransformNodiffManifest>,
 
   // #[napi(ts_type = "Array<import('esbuild').PartialMessage>")]
   pub errors: Vec<esbuild::PartialMessage>,
   // #[napi(ts_type = "Array<import('esbuild').PartialMessage>")]
   pub warnings: Vec<esbuild::PartialMessage>,
+  /// The machine-applicable fixes of the diagnostics above.
+  pub fixes: Vec<esbuild::Fix>,
+}
+
+/// The outputs of a dual transform, one for each thread.
+#[napi(object)]
+pub struct TransformNodiffDualOutput {
+  /// The output of the main thread, i.e.: `target: 'LEPUS'`.
+  pub lepus: TransformNodiffOutput,
+  /// The output of the background thread, i.e.: `target: 'JS'`.
+  pub js: TransformNodiffOutput,
 }
 
 /// A multi emitter that forwards to multiple emitters.
@@ -317,15 +517,66 @@ impl Emitter for MultiEmitter {
   }
 }
 
-pub struct TransformTask {
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct TransformBatchItem {
+  /// @public
+  /// The filename of the module, this overrides the `filename` in `options`.
+  pub filename: String,
   pub code: String,
-  pub options: TransformNodiffOptions,
+  pub options: Option<TransformNodiffOptions>,
 }
 
-fn transform_react_lynx_inner(
+pub fn transform_react_lynx_inner(
+  globals: &Globals,
   code: String,
   options: TransformNodiffOptions,
 ) -> TransformNodiffOutput {
+  transform_react_lynx_targets(globals, code, options, &[None])
+    .pop()
+    .unwrap()
+}
+
+/// Transform `code` for both the main thread and the background thread.
+///
+/// The module is parsed once and the shared passes (`resolver`, `typescript` and `dynamicImport`)
+/// run once, then each thread runs the rest of the passes on its own copy of the `Program`.
+/// See `TransformNodiffOptions::with_target` for the options that differ between the threads.
+pub fn transform_react_lynx_dual_inner(
+  globals: &Globals,
+  code: String,
+  options: TransformNodiffOptions,
+) -> TransformNodiffDualOutput {
+  let mut outputs = transform_react_lynx_targets(
+    globals,
+    code,
+    options,
+    &[Some(TransformTarget::LEPUS), Some(TransformTarget::JS)],
+  );
+  let js = outputs.pop().unwrap();
+  let lepus = outputs.pop().unwrap();
+
+  TransformNodiffDualOutput { lepus, js }
+}
+
+/// A deep copy of `comments`, `SingleThreadedComments::clone` shares the underlying maps.
+fn fork_comments(comments: &SingleThreadedComments) -> SingleThreadedComments {
+  let (leading, trailing) = comments.borrow_all();
+  SingleThreadedComments::from_leading_and_trailing(
+    Rc::new(RefCell::new(leading.clone())),
+    Rc::new(RefCell::new(trailing.clone())),
+  )
+}
+
+/// Run the transform once for each of the `targets`, `None` keeps the targets in `options`.
+///
+/// The parsing and the passes before `refresh` are shared by all the targets.
+fn transform_react_lynx_targets(
+  globals: &Globals,
+  code: String,
+  options: TransformNodiffOptions,
+  targets: &[Option<TransformTarget>],
+) -> Vec<TransformNodiffOutput> {
   let content_hash = match options.mode {
     Some(val) if val == TransformMode::Test => "test".into(),
     _ => calc_hash(code.as_str()),
@@ -338,11 +589,57 @@ fn transform_react_lynx_inner(
 
   let (esbuild_emitter, errors, warnings) =
     esbuild::EsbuildEmitter::new(options.plugin_name.clone(), Some(c.cm.clone()));
-  let esbuild_emitter = Box::new(esbuild_emitter);
+  let fixes = esbuild_emitter.fixes.clone();
+  let esbuild_emitter = Box::new(SeverityEmitter::new(
+    Box::new(esbuild_emitter),
+    options.diagnostics.clone().unwrap_or_default(),
+    options.all_warnings_as_errors.unwrap_or(false),
+  ));
   let emitter = Box::new(MultiEmitter::new(vec![esbuild_emitter]));
   let handler = Handler::with_emitter(true, false, emitter);
 
-  let result = GLOBALS.set(&Default::default(), || {
+  let orig = match get_input_source_map(
+    options
+      .input_source_map
+      .as_ref()
+      .unwrap_or(&BoolOr::Bool(false)),
+    &fm.src,
+  ) {
+    Ok(orig) => orig,
+    Err(err) => {
+      handler
+        .struct_warn(format!("Failed to read input source map: {}", err).as_str())
+        .code(lint(ids::INPUT_SOURCE_MAP))
+        .emit();
+      None
+    }
+  };
+
+  // The diagnostics of a target are the shared ones followed by its own.
+  fn collect<T: Clone>(messages: &RwLock<Vec<T>>, shared: usize, own: usize) -> Vec<T> {
+    let messages = messages.read().unwrap();
+    messages[..shared]
+      .iter()
+      .chain(&messages[own..])
+      .cloned()
+      .collect()
+  }
+  let diagnostics = |shared: (usize, usize, usize), own: (usize, usize, usize)| {
+    (
+      collect(&errors, shared.0, own.0),
+      collect(&warnings, shared.1, own.1),
+      collect(&fixes, shared.2, own.2),
+    )
+  };
+  let diagnostics_len = || {
+    (
+      errors.read().unwrap().len(),
+      warnings.read().unwrap().len(),
+      fixes.read().unwrap().len(),
+    )
+  };
+
+  GLOBALS.set(globals, || {
     let program = c.parse_js(
       fm,
       &handler,
@@ -354,406 +651,462 @@ fn transform_react_lynx_inner(
     let program = match program {
       Ok(program) => program,
       Err(_) => {
-        return TransformNodiffOutput {
-          code: "".into(),
-          map: None,
-          errors: errors.read().unwrap().clone(),
-          warnings: warnings.read().unwrap().clone(),
-        };
+        return targets
+          .iter()
+          .map(|_| TransformNodiffOutput {
+            code: "".into(),
+            map: None,
+            manifest: None,
+            errors: errors.read().unwrap().clone(),
+            warnings: warnings.read().unwrap().clone(),
+            fixes: fixes.read().unwrap().clone(),
+          })
+          .collect();
       }
     };
 
     let unresolved_mark = Mark::new();
     let top_level_mark = Mark::new();
 
-    let simplify_pass_1 = Optional::new(
-      simplifier(
-        top_level_mark,
-        simplify::Config {
-          dce: simplify::dce::Config {
-            preserve_imports_with_side_effects: false,
+    let (mut dynamic_import_visitor, dynamic_import_enabled) =
+      match options.dynamic_import.clone().unwrap_or(BoolOr::Bool(true)) {
+        BoolOr::Bool(config) => (
+          DynamicImportVisitor::new(Default::default(), Some(&comments)),
+          config,
+        ),
+        BoolOr::Value(config) => (DynamicImportVisitor::new(config, Some(&comments)), true),
+      };
+    let dynamic_import_plugin = Optional::new(
+      visit_mut_pass(&mut dynamic_import_visitor),
+      dynamic_import_enabled,
+    );
+
+    let shared_pass = (
+      panic_guard("fixer", fixer(Some(&comments))),
+      panic_guard("resolver", resolver(unresolved_mark, top_level_mark, true)),
+      panic_guard(
+        "typescript",
+        typescript::typescript(
+          typescript::Config {
+            verbatim_module_syntax: false,
+            import_not_used_as_values: typescript::ImportsNotUsedAsValues::Remove,
             ..Default::default()
           },
-          ..Default::default()
-        },
+          unresolved_mark,
+          top_level_mark,
+        ),
       ),
-      match &options.directive_dce {
-        Either::A(config) => *config,
-        Either::B(_) => true,
-      } || match &options.define_dce {
-        Either::A(config) => *config,
-        Either::B(_) => true,
-      },
+      panic_guard("dynamicImport", dynamic_import_plugin),
     );
 
-    let directive_dce_plugin = match options.directive_dce {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(DirectiveDCEVisitor::new(Default::default())),
-        config,
+    let mut program = Some(helpers::HELPERS.set(&helpers::Helpers::new(true), || {
+      HANDLER.set(&handler, || program.apply(shared_pass))
+    }));
+    let pragmas = HANDLER.set(&handler, || match program.as_ref().unwrap() {
+      Program::Module(module) => Pragmas::parse(
+        &comments,
+        iter::once(module.span).chain(module.body.iter().map(|item| item.span())),
       ),
-      Either::B(config) => Optional::new(visit_mut_pass(DirectiveDCEVisitor::new(config)), true),
-    };
+      Program::Script(script) => Pragmas::parse(
+        &comments,
+        iter::once(script.span).chain(script.body.iter().map(|stmt| stmt.span())),
+      ),
+    });
+    let shared_diagnostics = diagnostics_len();
+
+    let mut outputs = Vec::with_capacity(targets.len());
+    for (index, target) in targets.iter().enumerate() {
+      let is_last = index + 1 == targets.len();
+      let options = match target {
+        Some(target) => options.clone().with_target(*target),
+        None => options.clone(),
+      };
+      // The last target takes the shared `Program`, the others work on copies.
+      let mut program = if is_last {
+        program.take().unwrap()
+      } else {
+        program.clone().unwrap()
+      };
+      // `@lynxTarget` keeps the file off the other thread, only its exports are left.
+      if let (Some(lynx_target), Some(thread_target)) =
+        (pragmas.lynx_target, options.thread_target())
+      {
+        if lynx_target != thread_target {
+          eliminate_module(&mut program);
+        }
+      }
+      let comments = fork_comments(&comments);
+      let own_diagnostics = diagnostics_len();
 
-    let define_dce_plugin = {
-      let opts = GlobalPassOption {
-        vars: match &options.define_dce {
-          Either::A(_) => Default::default(),
-          Either::B(config) => {
-            let mut map = indexmap::IndexMap::<_, _, FxBuildHasher>::default();
-            for (key, value) in &config.define {
-              map.insert(key.as_str().into(), value.as_str().into());
-            }
-            map
-          }
+      let simplify_pass_1 = Optional::new(
+        simplifier(
+          top_level_mark,
+          simplify::Config {
+            dce: simplify::dce::Config {
+              preserve_imports_with_side_effects: false,
+              ..Default::default()
+            },
+            ..Default::default()
+          },
+        ),
+        match &options.directive_dce {
+          BoolOr::Bool(config) => *config,
+          BoolOr::Value(_) => true,
+        } || match &options.define_dce {
+          BoolOr::Bool(config) => *config,
+          BoolOr::Value(_) => true,
         },
-        envs: Default::default(),
-        typeofs: Default::default(),
+      );
+
+      let directive_dce_plugin = match options.directive_dce {
+        BoolOr::Bool(config) => Optional::new(
+          visit_mut_pass(DirectiveDCEVisitor::new(Default::default())),
+          config,
+        ),
+        BoolOr::Value(config) => {
+          Optional::new(visit_mut_pass(DirectiveDCEVisitor::new(config)), true)
+        }
       };
 
-      Optional::new(
-        opts.build(&cm, &handler),
-        matches!(options.define_dce, Either::B(_)),
-      )
-    };
+      let define_dce_plugin = {
+        let opts = GlobalPassOption {
+          vars: match &options.define_dce {
+            BoolOr::Bool(_) => Default::default(),
+            BoolOr::Value(config) => {
+              let mut map = indexmap::IndexMap::<_, _, FxBuildHasher>::default();
+              for (key, value) in &config.define {
+                map.insert(key.as_str().into(), value.as_str().into());
+              }
+              map
+            }
+          },
+          envs: Default::default(),
+          typeofs: Default::default(),
+        };
 
-    let css_scope_plugin = match options.css_scope {
-      Either::A(enabled) => Optional::new(
-        visit_mut_pass(CSSScopeVisitor::new(
-          CSSScopeVisitorConfig::default(),
-          Some(&comments),
-        )),
-        enabled,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(CSSScopeVisitor::new(config, Some(&comments))),
-        true,
-      ),
-    };
+        Optional::new(
+          opts.build(&cm, &handler),
+          matches!(options.define_dce, BoolOr::Value(_)),
+        )
+      };
 
-    let (snapshot_plugin_config, enabled) = match &options.snapshot.unwrap_or(Either::A(true)) {
-      Either::A(config) => (
-        JSXTransformerConfig {
-          filename: options.filename,
-          ..Default::default()
-        },
-        *config,
-      ),
-      Either::B(config) => (config.clone(), true),
-    };
+      let css_scope_plugin = match options.css_scope {
+        BoolOr::Bool(enabled) => Optional::new(
+          visit_mut_pass(CSSScopeVisitor::new(
+            CSSScopeVisitorConfig::default(),
+            Some(&comments),
+          )),
+          enabled,
+        ),
+        BoolOr::Value(config) => Optional::new(
+          visit_mut_pass(CSSScopeVisitor::new(config, Some(&comments))),
+          true,
+        ),
+      };
 
-    let react_transformer = Optional::new(
-      react::react(
-        cm.clone(),
-        Some(&comments),
-        react::Options {
-          next: Some(false),
-          runtime: Some(react::Runtime::Automatic),
-          import_source: snapshot_plugin_config
-            .jsx_import_source
-            .clone()
-            .map(|s| Atom::from(s)),
-          pragma: None,
-          pragma_frag: None,
-          // We may want `main-thread:foo={fooMainThreadFunc}` to work
-          throw_if_namespace: Some(false),
-          development: Some(matches!(options.mode, Some(TransformMode::Development))),
-          refresh: None,
-          ..Default::default()
-        },
-        top_level_mark,
-        unresolved_mark,
-      ),
-      enabled && !snapshot_plugin_config.preserve_jsx,
-    );
+      let (mut snapshot_plugin_config, enabled) =
+        match &options.snapshot.unwrap_or(BoolOr::Bool(true)) {
+          BoolOr::Bool(config) => (
+            JSXTransformerConfig {
+              filename: options.filename.clone(),
+              ..Default::default()
+            },
+            *config,
+          ),
+          BoolOr::Value(config) => (config.clone(), true),
+        };
+      if let Some(source) = &pragmas.jsx_import_source {
+        snapshot_plugin_config.jsx_import_source = Some(source.clone());
+        snapshot_plugin_config.runtime_pkg = source.clone();
+      }
+      if pragmas.jsx_preserve {
+        snapshot_plugin_config.preserve_jsx = true;
+      }
 
-    let snapshot_plugin = Optional::new(
-      visit_mut_pass(
-        JSXTransformer::new(
-          snapshot_plugin_config,
+      let react_transformer = Optional::new(
+        react::react(
           cm.clone(),
           Some(&comments),
+          react::Options {
+            next: Some(false),
+            runtime: Some(match pragmas.jsx_runtime {
+              Some(JsxRuntime::Classic) => react::Runtime::Classic,
+              Some(JsxRuntime::Automatic) | None => react::Runtime::Automatic,
+            }),
+            import_source: snapshot_plugin_config
+              .jsx_import_source
+              .clone()
+              .map(|s| Atom::from(s)),
+            pragma: None,
+            pragma_frag: None,
+            // We may want `main-thread:foo={fooMainThreadFunc}` to work
+            throw_if_namespace: Some(false),
+            development: Some(matches!(options.mode, Some(TransformMode::Development))),
+            refresh: None,
+            ..Default::default()
+          },
           top_level_mark,
           unresolved_mark,
-          options.mode.unwrap_or(TransformMode::Production),
-        )
-        .with_content_hash(content_hash.clone()),
-      ),
-      enabled,
-    );
+        ),
+        enabled && !snapshot_plugin_config.preserve_jsx,
+      );
 
-    let shake_plugin = match options.shake.clone() {
-      Either::A(config) => Optional::new(visit_mut_pass(ShakeVisitor::default()), config),
-      Either::B(config) => Optional::new(visit_mut_pass(ShakeVisitor::new(config)), true),
-    };
+      let mut snapshot_visitor = JSXTransformer::new(
+        snapshot_plugin_config,
+        cm.clone(),
+        Some(&comments),
+        top_level_mark,
+        unresolved_mark,
+        options.mode.unwrap_or(TransformMode::Production),
+      )
+      .with_content_hash(content_hash.clone())
+      .with_pragmas(pragmas.clone());
+      let snapshot_plugin = Optional::new(visit_mut_pass(&mut snapshot_visitor), enabled);
+
+      let shake_plugin = match options.shake.clone() {
+        BoolOr::Bool(config) => Optional::new(visit_mut_pass(ShakeVisitor::default()), config),
+        BoolOr::Value(config) => Optional::new(visit_mut_pass(ShakeVisitor::new(config)), true),
+      };
 
-    let simplify_pass = simplifier(
-      top_level_mark,
-      simplify::Config {
-        dce: simplify::dce::Config {
-          preserve_imports_with_side_effects: false,
+      let simplify_pass = simplifier(
+        top_level_mark,
+        simplify::Config {
+          dce: simplify::dce::Config {
+            preserve_imports_with_side_effects: false,
+            ..Default::default()
+          },
           ..Default::default()
         },
-        ..Default::default()
-      },
-    );
-
-    let compat_plugin = match options.compat.clone() {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(CompatVisitor::new(
-          CompatVisitorConfig::default(),
-          Some(&comments),
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(CompatVisitor::new(config, Some(&comments))),
-        true,
-      ),
-    };
+      );
+
+      let compat_plugin = match options.compat.clone() {
+        BoolOr::Bool(config) => Optional::new(
+          visit_mut_pass(CompatVisitor::new(
+            CompatVisitorConfig::default(),
+            Some(&comments),
+          )),
+          config,
+        ),
+        BoolOr::Value(config) => Optional::new(
+          visit_mut_pass(CompatVisitor::new(config, Some(&comments))),
+          true,
+        ),
+      };
 
-    let compat_post_plugin = match options.compat {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(CompatPostVisitor::new(
-          Default::default(),
-          unresolved_mark,
-          top_level_mark,
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(CompatPostVisitor::new(
+      let compat_post_plugin = match options.compat {
+        BoolOr::Bool(config) => Optional::new(
+          visit_mut_pass(CompatPostVisitor::new(
+            Default::default(),
+            unresolved_mark,
+            top_level_mark,
+          )),
           config,
-          unresolved_mark,
-          top_level_mark,
-        )),
-        true,
-      ),
-    };
+        ),
+        BoolOr::Value(config) => Optional::new(
+          visit_mut_pass(CompatPostVisitor::new(
+            config,
+            unresolved_mark,
+            top_level_mark,
+          )),
+          true,
+        ),
+      };
 
-    let refresh_plugin = match options.refresh {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(RefreshVisitor::new(
-          RefreshVisitorConfig::default(),
-          content_hash.clone(),
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(RefreshVisitor::new(config, content_hash.clone())),
-        true,
-      ),
-    };
+      let refresh_plugin = match options.refresh {
+        BoolOr::Bool(config) => Optional::new(
+          visit_mut_pass(RefreshVisitor::new(
+            RefreshVisitorConfig::default(),
+            content_hash.clone(),
+          )),
+          config,
+        ),
+        BoolOr::Value(config) => Optional::new(
+          visit_mut_pass(RefreshVisitor::new(config, content_hash.clone())),
+          true,
+        ),
+      };
 
-    let worklet_plugin = match options.worklet {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(WorkletVisitor::default().with_content_hash(content_hash)),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(
+      let (mut worklet_visitor, worklet_enabled) = match options.worklet {
+        BoolOr::Bool(config) => (
+          WorkletVisitor::default().with_content_hash(content_hash.clone()),
+          config,
+        ),
+        BoolOr::Value(config) => (
           WorkletVisitor::new(options.mode.unwrap_or(TransformMode::Production), config)
-            .with_content_hash(content_hash),
+            .with_content_hash(content_hash.clone()),
+          true,
         ),
-        true,
-      ),
-    };
-
-    let dynamic_import_plugin = match options.dynamic_import.unwrap_or(Either::A(true)) {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(DynamicImportVisitor::new(
-          Default::default(),
-          Some(&comments),
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(DynamicImportVisitor::new(config, Some(&comments))),
-        true,
-      ),
-    };
+      };
+      let worklet_plugin = Optional::new(visit_mut_pass(&mut worklet_visitor), worklet_enabled);
+
+      let inject_plugin = match options.inject.unwrap_or(BoolOr::Bool(false)) {
+        BoolOr::Bool(config) => Optional::new(
+          visit_mut_pass(InjectVisitor::new(
+            Default::default(),
+            unresolved_mark,
+            top_level_mark,
+          )),
+          config,
+        ),
+        BoolOr::Value(config) => Optional::new(
+          visit_mut_pass(InjectVisitor::new(config, unresolved_mark, top_level_mark)),
+          true,
+        ),
+      };
 
-    let inject_plugin = match options.inject.unwrap_or(Either::A(false)) {
-      Either::A(config) => Optional::new(
-        visit_mut_pass(InjectVisitor::new(
-          Default::default(),
-          unresolved_mark,
-          top_level_mark,
-        )),
-        config,
-      ),
-      Either::B(config) => Optional::new(
-        visit_mut_pass(InjectVisitor::new(config, unresolved_mark, top_level_mark)),
-        true,
-      ),
-    };
+      let pass = (
+        panic_guard("refresh", refresh_plugin),
+        panic_guard("compat", compat_plugin),
+        panic_guard("worklet", worklet_plugin),
+        panic_guard("cssScope", css_scope_plugin),
+        panic_guard("snapshot", snapshot_plugin),
+        panic_guard("directiveDCE", directive_dce_plugin),
+        panic_guard("defineDCE", define_dce_plugin),
+        // do simplify after DCE above to make shake below works better
+        panic_guard("simplify", simplify_pass_1),
+        (
+          panic_guard("shake", shake_plugin),
+          panic_guard("simplify", simplify_pass),
+          panic_guard("react", react_transformer),
+          // TODO(hongzhiyuan.hzy): if `ident` we added above is correctly marked, this pass will be unnecessary
+          panic_guard("resolver", resolver(unresolved_mark, top_level_mark, true)),
+          panic_guard("compatPost", compat_post_plugin),
+          panic_guard("inject", inject_plugin),
+          panic_guard(
+            "hygiene",
+            hygiene_with_config(Config {
+              top_level_mark,
+              ..Default::default()
+            }),
+          ),
+          panic_guard("fixer", fixer(Some(&comments))),
+        ),
+      );
 
-    let pass = (
-      &mut fixer(Some(&comments)),
-      resolver(unresolved_mark, top_level_mark, true),
-      typescript::typescript(
-        typescript::Config {
-          verbatim_module_syntax: false,
-          import_not_used_as_values: typescript::ImportsNotUsedAsValues::Remove,
-          ..Default::default()
-        },
-        unresolved_mark,
-        top_level_mark,
-      ),
-      dynamic_import_plugin,
-      refresh_plugin,
-      compat_plugin,
-      worklet_plugin,
-      css_scope_plugin,
-      snapshot_plugin,
-      directive_dce_plugin,
-      define_dce_plugin,
-      simplify_pass_1, // do simplify after DCE above to make shake below works better
-      (
-        shake_plugin,
-        simplify_pass,
-        react_transformer,
-        // TODO(hongzhiyuan.hzy): if `ident` we added above is correctly marked, this pass will be unnecessary
-        resolver(unresolved_mark, top_level_mark, true),
-        compat_post_plugin,
-        inject_plugin,
-        hygiene_with_config(Config {
-          top_level_mark,
-          ..Default::default()
-        }),
-        &mut fixer(Some(&comments)),
-      ),
-    );
+      let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
+        HANDLER.set(&handler, || program.apply(pass))
+      });
 
-    let program = helpers::HELPERS.set(&helpers::Helpers::new(true), || {
-      HANDLER.set(&handler, || program.apply(pass))
-    });
+      let manifest = TransformNodiffManifest {
+        snapshots: snapshot_visitor.manifest,
+        worklets: worklet_visitor.manifest,
+        dynamic_imports: dynamic_import_visitor.manifest.clone(),
+      };
 
-    let result = c.print(
-      &program,
-      PrintArgs {
-        output: None,
-        source_root: "".into(), // TODO: add root
-        source_file_name: options.source_file_name.as_ref().map(String::as_str),
-        source_map_url: None,
-        output_path: None,
-        inline_sources_content: options.inline_sources_content.unwrap_or(true),
-        source_map: match options.sourcemap {
-          Either::A(b) => SourceMapsConfig::Bool(b),
-          Either::B(s) => SourceMapsConfig::Str(s),
+      let result = c.print(
+        &program,
+        PrintArgs {
+          output: None,
+          source_root: "".into(), // TODO: add root
+          source_file_name: options.source_file_name.as_ref().map(String::as_str),
+          source_map_url: None,
+          output_path: None,
+          inline_sources_content: options.inline_sources_content.unwrap_or(true),
+          source_map: match options.sourcemap {
+            BoolOr::Bool(b) => SourceMapsConfig::Bool(b),
+            BoolOr::Value(s) => SourceMapsConfig::Str(s),
+          },
+          source_map_names: &Default::default(),
+          orig: orig.as_ref(),
+          comments: Some(&comments),
+          emit_source_map_columns: options.source_map_columns.unwrap_or(true),
+          preamble: "".into(),
+          codegen_config: codegen::Config::default()
+            .with_target(EsVersion::latest())
+            .with_minify(false)
+            .with_ascii_only(false),
         },
-        source_map_names: &Default::default(),
-        orig: None,
-        comments: Some(&comments),
-        emit_source_map_columns: options.source_map_columns.unwrap_or(true),
-        preamble: "".into(),
-        codegen_config: codegen::Config::default()
-          .with_target(EsVersion::latest())
-          .with_minify(false)
-          .with_ascii_only(false),
-      },
-    );
-
-    match result {
-      Ok(result) => TransformNodiffOutput {
-        code: result.code,
-        map: result.map,
-        errors: vec![],
-        warnings: vec![],
-      },
-      Err(_) => {
-        return TransformNodiffOutput {
+      );
+
+      let (errors, warnings, fixes) = diagnostics(shared_diagnostics, own_diagnostics);
+      outputs.push(match result {
+        Ok(result) => TransformNodiffOutput {
+          code: result.code,
+          map: result.map,
+          manifest: Some(manifest),
+          errors,
+          warnings,
+          fixes,
+        },
+        Err(_) => TransformNodiffOutput {
           code: "".into(),
           map: None,
-          errors: errors.read().unwrap().clone(),
-          warnings: warnings.read().unwrap().clone(),
-        };
-      }
+          manifest: None,
+          errors,
+          warnings,
+          fixes,
+        },
+      });
     }
-  });
-
-  let r = TransformNodiffOutput {
-    code: result.code,
-    map: result.map,
-    errors: errors.read().unwrap().clone(),
-    warnings: warnings.read().unwrap().clone(),
-  };
 
-  r
+    outputs
+  })
 }
 
-#[napi]
-pub fn transform_react_lynx_sync(
-  _env: Env,
-  code: String,
-  options: Option<TransformNodiffOptions>,
-) -> napi::Result<TransformNodiffOutput> {
-  let out = transform_react_lynx_inner(code, options.unwrap_or_default());
-  napi::Result::Ok(out)
-}
-
-#[napi]
-impl Task for TransformTask {
-  type Output = TransformNodiffOutput;
-  type JsValue = TransformNodiffOutput;
-  fn compute(&mut self) -> napi::Result<Self::Output> {
-    let out = transform_react_lynx_inner(self.code.clone(), self.options.clone());
-    Ok(out)
-  }
-  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
-    napi::Result::Ok(output)
-  }
-}
-
-#[napi]
-pub fn transform_react_lynx(
-  _env: Env,
-  code: String,
-  options: Option<TransformNodiffOptions>,
-) -> napi::Result<AsyncTask<TransformTask>> {
-  Ok(AsyncTask::new(TransformTask {
-    code,
-    options: options.unwrap_or_default(),
-  }))
-}
-
-pub struct BundleTransformTask {
-  pub code: String,
-  pub options: bundle::TransformNodiffBundleOptions,
-}
+/// Transform all the `items` with a pool of worker threads.
+///
+/// Each worker owns one swc [`Globals`] and reuses it for every item it picks up.
+/// The outputs are returned in the same order as the `items`.
+pub fn transform_react_lynx_batch_inner(
+  items: Vec<TransformBatchItem>,
+) -> Vec<TransformNodiffOutput> {
+  let items = items
+    .into_iter()
+    .map(|item| {
+      let mut options = item.options.unwrap_or_default();
+      options.filename = item.filename;
+      (item.code, options)
+    })
+    .collect::<Vec<_>>();
+
+  // There is no thread on `wasm32-unknown-unknown`, run all the items on the current thread.
+  let concurrency = if cfg!(target_arch = "wasm32") {
+    1
+  } else {
+    std::thread::available_parallelism()
+      .map(|n| n.get())
+      .unwrap_or(1)
+      .min(items.len())
+  };
 
-#[napi]
-impl Task for BundleTransformTask {
-  type Output = bundle::TransformNodiffBundleOutput;
-  type JsValue = bundle::TransformNodiffBundleOutput;
-  fn compute(&mut self) -> napi::Result<Self::Output> {
-    let out = bundle::transform_bundle_result_inner(self.code.clone(), self.options.clone());
-    Ok(out)
+  if concurrency <= 1 {
+    let globals = Globals::new();
+    return items
+      .into_iter()
+      .map(|(code, options)| transform_react_lynx_inner(&globals, code, options))
+      .collect();
   }
-  fn resolve(&mut self, _: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
-    napi::Result::Ok(output)
-  }
-}
 
-#[napi]
-pub fn transform_bundle_result_sync(
-  _env: Env,
-  code: String,
-  options: Option<bundle::TransformNodiffBundleOptions>,
-) -> napi::Result<bundle::TransformNodiffBundleOutput> {
-  let out = bundle::transform_bundle_result_inner(code, options.unwrap_or_default());
-  napi::Result::Ok(out)
-}
+  let len = items.len();
+  let next = std::sync::atomic::AtomicUsize::new(0);
+  let items = items
+    .into_iter()
+    .map(Some)
+    .map(Mutex::new)
+    .collect::<Vec<_>>();
+  let outputs = (0..len).map(|_| Mutex::new(None)).collect::<Vec<_>>();
+
+  std::thread::scope(|scope| {
+    for _ in 0..concurrency {
+      scope.spawn(|| {
+        let globals = Globals::new();
+        loop {
+          let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+          if index >= len {
+            break;
+          }
+          let (code, options) = items[index].lock().unwrap().take().unwrap();
+          let output = transform_react_lynx_inner(&globals, code, options);
+          *outputs[index].lock().unwrap() = Some(output);
+        }
+      });
+    }
+  });
 
-#[napi]
-pub fn transform_bundle_result(
-  _env: Env,
-  code: String,
-  options: Option<bundle::TransformNodiffBundleOptions>,
-) -> napi::Result<AsyncTask<BundleTransformTask>> {
-  Ok(AsyncTask::new(BundleTransformTask {
-    code,
-    options: options.unwrap_or_default(),
-  }))
+  outputs
+    .into_iter()
+    .map(|output| output.into_inner().unwrap().unwrap())
+    .collect()
 }
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
@@ -775,8 +1128,6 @@ mod wasm {
 
   register_custom_getrandom!(custom_getrandom);
 
-  use ::napi::{JsObject, NapiValue};
-
   const fn max(a: usize, b: usize) -> usize {
     [a, b][(a < b) as usize]
   }
@@ -810,23 +1161,6 @@ mod wasm {
 
     std::alloc::dealloc(ptr, layout);
   }
-
-  #[no_mangle]
-  pub unsafe extern "C" fn napi_register_wasm_v1(
-    raw_env: napi::sys::napi_env,
-    raw_exports: napi::sys::napi_value,
-  ) {
-    // let env = Env::from_raw(raw_env);
-    let mut exports = JsObject::from_raw_unchecked(raw_env, raw_exports);
-
-    #[cfg_attr(rustfmt, rustfmt_skip)]
-    {
-      let _ = exports.create_named_method("transformReactLynxSync", crate::__napi__transform_react_lynx_sync);
-      let _ = exports.create_named_method("transformBundleResultSync", crate::__napi__transform_bundle_result_sync);
-      let _ = exports.create_named_method("transformReactLynx", crate::__napi__transform_react_lynx);
-      let _ = exports.create_named_method("transformBundleResult", crate::__napi__transform_bundle_result);
-    }
-  }
 }
 
 #[cfg(test)]
@@ -847,4 +1181,1017 @@ mod tests {
     assert_eq!(s.typescript(), true);
     assert_eq!(s.decorators(), false); // default to false
   }
+
+  #[test]
+  fn test_transform_batch_keeps_order() {
+    use super::*;
+
+    let items = (0..16)
+      .map(|i| TransformBatchItem {
+        filename: format!("{i}.js"),
+        code: format!("export const v{i} = {i};"),
+        options: None,
+      })
+      .collect::<Vec<_>>();
+
+    let outputs = transform_react_lynx_batch_inner(items);
+
+    assert_eq!(outputs.len(), 16);
+    for (i, output) in outputs.iter().enumerate() {
+      assert!(output.errors.is_empty());
+      assert!(output.code.contains(&format!("export const v{i} = {i};")));
+    }
+  }
+
+  #[test]
+  fn test_input_source_map() {
+    use super::*;
+    use swc_core::base::sourcemap;
+
+    let mut builder = sourcemap::SourceMapBuilder::new(None);
+    let src_id = builder.add_source("original.ts");
+    builder.set_source_contents(src_id, Some("export const foo: number = 1;"));
+    builder.add_raw(0, 0, 0, 0, Some(src_id), None, false);
+    builder.add_raw(0, 13, 0, 13, Some(src_id), None, false);
+    let mut input_source_map = vec![];
+    builder
+      .into_sourcemap()
+      .to_writer(&mut input_source_map)
+      .unwrap();
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      "export const foo = 1;".into(),
+      TransformNodiffOptions {
+        filename: "intermediate.js".into(),
+        sourcemap: BoolOr::Bool(true),
+        input_source_map: Some(BoolOr::Value(String::from_utf8(input_source_map).unwrap())),
+        ..Default::default()
+      },
+    );
+
+    assert!(output.errors.is_empty());
+    let map = sourcemap::SourceMap::from_slice(output.map.unwrap().as_bytes()).unwrap();
+    assert_eq!(map.sources().collect::<Vec<_>>(), vec!["original.ts"]);
+  }
+
+  #[test]
+  fn test_manifest() {
+    use super::*;
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"
+import("./foo.js");
+function App() {
+  function onTap(e) {
+    "main thread";
+    console.log(e, bar);
+  }
+  return <view class={cls} main-thread:bindtap={onTap}><text>Hello</text>{children}</view>;
+}
+"#
+      .into(),
+      TransformNodiffOptions {
+        filename: "index.jsx".into(),
+        mode: Some(TransformMode::Test),
+        worklet: BoolOr::Value(WorkletVisitorConfig {
+          filename: "index.jsx".into(),
+          target: target::TransformTarget::LEPUS,
+          custom_global_ident_names: None,
+          runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
+        }),
+        dynamic_import: Some(BoolOr::Value(DynamicImportVisitorConfig {
+          layer: "main-thread".into(),
+          ..Default::default()
+        })),
+        ..Default::default()
+      },
+    );
+
+    assert!(output.errors.is_empty());
+    let manifest = output.manifest.unwrap();
+
+    assert_eq!(manifest.snapshots.len(), 1);
+    let snapshot = &manifest.snapshots[0];
+    assert!(snapshot.uid.starts_with("__snapshot_"));
+    assert_eq!(snapshot.elements.len(), 1);
+    assert_eq!(snapshot.elements[0].tag, "view");
+    assert_eq!(snapshot.elements[0].children[0].tag, "text");
+    assert_eq!(
+      snapshot.elements[0].children[0].children[0].text,
+      Some("Hello".into())
+    );
+    assert_eq!(
+      snapshot
+        .dynamic_parts
+        .iter()
+        .map(|part| (part.kind.as_str(), part.attr.clone()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("attr", Some("class".into())),
+        ("attr", Some("main-thread:bindEvent:tap".into())),
+        ("children", None),
+      ]
+    );
+
+    assert_eq!(manifest.worklets.len(), 1);
+    assert_eq!(manifest.worklets[0].worklet_type, "main-thread");
+    assert_eq!(manifest.worklets[0].captured_idents, vec!["bar"]);
+
+    assert_eq!(manifest.dynamic_imports.len(), 1);
+    assert_eq!(manifest.dynamic_imports[0].specifier, "./foo.js");
+    assert_eq!(manifest.dynamic_imports[0].layer, "main-thread");
+  }
+
+  #[test]
+  fn test_panic_as_diagnostic() {
+    use super::*;
+
+    let transform = |code: &str| {
+      transform_react_lynx_inner(
+        &Globals::new(),
+        code.into(),
+        TransformNodiffOptions {
+          filename: "index.jsx".into(),
+          ..Default::default()
+        },
+      )
+    };
+
+    // This intentionally relies on the `todo!()` of `AttrName::from_ns` for an unknown
+    // `main-thread:*` attribute to panic inside the `snapshot` pass, replace the input with another
+    // panic once that is implemented.
+    let output = transform(
+      "export function App() {\n  return <view><text main-thread:foo={bar}>Hello</text></view>;\n}",
+    );
+    assert_eq!(output.errors.len(), 1);
+    let error = &output.errors[0];
+    assert!(error
+      .text
+      .as_deref()
+      .unwrap()
+      .starts_with("Internal error in `snapshot`"));
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file.as_deref(), Some("index.jsx"));
+    assert_eq!(location.line, Some(2));
+    assert_eq!(location.column, Some(15));
+  }
+
+  #[test]
+  fn test_diagnostic_severities() {
+    use super::*;
+
+    let transform = |diagnostics: &[(&str, DiagnosticSeverity)], all_warnings_as_errors| {
+      transform_react_lynx_inner(
+        &Globals::new(),
+        "export const App = () => <view><text key='1' /></view>;\nexport const style = <view style={{ fooBar: x }} />;".into(),
+        TransformNodiffOptions {
+          filename: "index.jsx".into(),
+          diagnostics: Some(
+            diagnostics
+              .iter()
+              .map(|(id, severity)| (id.to_string(), *severity))
+              .collect(),
+          ),
+          all_warnings_as_errors: Some(all_warnings_as_errors),
+          ..Default::default()
+        },
+      )
+    };
+    let ids = |messages: &[PartialMessage]| {
+      messages
+        .iter()
+        .map(|message| message.id.clone().unwrap())
+        .collect::<Vec<_>>()
+    };
+
+    let output = transform(&[], false);
+    assert!(output.errors.is_empty());
+    assert_eq!(
+      ids(&output.warnings),
+      vec![diagnostic_ids::KEY_NOT_ON_ROOT, diagnostic_ids::EXTRACT_CSS]
+    );
+
+    let output = transform(
+      &[
+        (diagnostic_ids::KEY_NOT_ON_ROOT, DiagnosticSeverity::Error),
+        (diagnostic_ids::EXTRACT_CSS, DiagnosticSeverity::Off),
+      ],
+      false,
+    );
+    assert_eq!(ids(&output.errors), vec![diagnostic_ids::KEY_NOT_ON_ROOT]);
+    assert!(output.warnings.is_empty());
+
+    let output = transform(
+      &[(diagnostic_ids::EXTRACT_CSS, DiagnosticSeverity::Warn)],
+      true,
+    );
+    assert_eq!(ids(&output.errors), vec![diagnostic_ids::KEY_NOT_ON_ROOT]);
+    assert_eq!(ids(&output.warnings), vec![diagnostic_ids::EXTRACT_CSS]);
+  }
+
+  #[test]
+  fn test_compat_fixes() {
+    use super::*;
+
+    let code = r#"import { View } from "@lynx-js/react-components";
+import { Component } from "@lynx-js/react-runtime";
+export class App extends Component {
+  render() {
+    return <View lynx-key="a" onClick={this.onClick} />;
+  }
+}"#;
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      code.into(),
+      TransformNodiffOptions {
+        filename: "index.jsx".into(),
+        compat: BoolOr::Value(CompatVisitorConfig {
+          target: TransformTarget::JS,
+          ..Default::default()
+        }),
+        ..Default::default()
+      },
+    );
+
+    assert_eq!(output.warnings.len(), 5);
+    assert_eq!(
+      output
+        .fixes
+        .iter()
+        .map(|fix| fix.id.as_deref().unwrap())
+        .collect::<Vec<_>>(),
+      vec![
+        diagnostic_ids::DEPRECATED_RUNTIME_PKG,
+        diagnostic_ids::DEPRECATED_ELEMENT_NAME,
+        diagnostic_ids::DEPRECATED_LYNX_KEY,
+        diagnostic_ids::DEPRECATED_EVENT_PROPS,
+      ]
+    );
+    assert_eq!(
+      apply_fixes(code, &output.fixes),
+      r#"import { View } from "@lynx-js/react-components";
+import { Component } from "@lynx-js/react";
+export class App extends Component {
+  render() {
+    return <view key="a" bindtap={this.onClick} />;
+  }
+}"#
+    );
+  }
+
+  #[test]
+  fn test_transform_dual() {
+    use super::*;
+
+    let code = r#"
+import type { FC } from "@lynx-js/react";
+export function App() {
+  function onTap(e) {
+    "main thread";
+    console.log(e);
+  }
+  if (__LEPUS__) {
+    console.log("main thread");
+  }
+  return <view main-thread:bindtap={onTap}><text>{__BACKGROUND__ ? "bg" : "mt"}</text></view>;
+}
+"#;
+    let options = TransformNodiffOptions {
+      filename: "index.tsx".into(),
+      worklet: BoolOr::Value(WorkletVisitorConfig {
+        filename: "index.tsx".into(),
+        ..Default::default()
+      }),
+      define_dce: BoolOr::Value(DefineDCEVisitorConfig {
+        define: Default::default(),
+      }),
+      directive_dce: BoolOr::Bool(true),
+      ..Default::default()
+    };
+
+    let TransformNodiffDualOutput { lepus, js } =
+      transform_react_lynx_dual_inner(&Globals::new(), code.into(), options.clone());
+    assert!(lepus.errors.is_empty());
+    assert!(js.errors.is_empty());
+
+    // Same as transforming each target separately.
+    for (output, target) in [(&lepus, TransformTarget::LEPUS), (&js, TransformTarget::JS)] {
+      let single = transform_react_lynx_inner(
+        &Globals::new(),
+        code.into(),
+        options.clone().with_target(target),
+      );
+      assert_eq!(output.code, single.code);
+    }
+
+    assert!(lepus.code.contains(r#"console.log("main thread")"#));
+    assert!(!js.code.contains(r#"console.log("main thread")"#));
+
+    // The snapshots and worklets are referenced across the threads.
+    let lepus = lepus.manifest.unwrap();
+    let js = js.manifest.unwrap();
+    let uids = |manifest: &TransformNodiffManifest| {
+      manifest
+        .snapshots
+        .iter()
+        .map(|snapshot| snapshot.uid.clone())
+        .collect::<Vec<_>>()
+    };
+    let hashes = |manifest: &TransformNodiffManifest| {
+      manifest
+        .worklets
+        .iter()
+        .map(|worklet| worklet.hash.clone())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(uids(&lepus).len(), 1);
+    assert_eq!(uids(&lepus), uids(&js));
+    assert_eq!(hashes(&lepus).len(), 1);
+    assert_eq!(hashes(&lepus), hashes(&js));
+  }
+
+  #[test]
+  fn test_snapshot_fingerprint_uid() {
+    use super::*;
+
+    let transform = |filename: &str, code: &str| {
+      let options = TransformNodiffOptions {
+        filename: filename.into(),
+        snapshot: Some(BoolOr::Value(JSXTransformerConfig {
+          filename: filename.into(),
+          fingerprint_uid: Some(true),
+          ..Default::default()
+        })),
+        ..Default::default()
+      };
+      let TransformNodiffDualOutput { lepus, js } =
+        transform_react_lynx_dual_inner(&Globals::new(), code.into(), options);
+      assert!(lepus.errors.is_empty());
+      assert!(js.errors.is_empty());
+      (lepus.manifest.unwrap(), js.manifest.unwrap())
+    };
+
+    let (a_lepus, a_js) = transform(
+      "a.jsx",
+      "export const A = ({ a }) => a ? <view><text>{a}</text></view> : <view><text>{a}</text></view>;",
+    );
+    let (b_lepus, b_js) = transform(
+      "b.jsx",
+      "export const B = ({ b }) => <view><text>{b}</text></view>;",
+    );
+
+    // The identical snapshots in the same module are emitted once.
+    assert_eq!(a_lepus.snapshots.len(), 1);
+    assert_eq!(a_js.snapshots.len(), 1);
+    assert_eq!(b_lepus.snapshots.len(), 1);
+
+    // The uid only depends on the structure, across the modules and the threads.
+    let a = &a_lepus.snapshots[0];
+    let b = &b_lepus.snapshots[0];
+    assert_eq!(a.uid, format!("__snapshot_{}", a.fingerprint));
+    assert_eq!(a.uid, b.uid);
+    assert_eq!(a.uid, a_js.snapshots[0].uid);
+    assert_eq!(b.uid, b_js.snapshots[0].uid);
+  }
+
+  #[test]
+  fn test_worklet_source_map() {
+    use super::*;
+    use swc_core::base::sourcemap;
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"
+const onTap = (e) => {
+  'main thread';
+  e.currentTarget.setStyleProperty('color', color);
+};
+"#
+      .into(),
+      TransformNodiffOptions {
+        sourcemap: BoolOr::Bool(true),
+        worklet: BoolOr::Value(WorkletVisitorConfig {
+          target: TransformTarget::LEPUS,
+          ..Default::default()
+        }),
+        ..Default::default()
+      },
+    );
+
+    assert!(output.errors.is_empty());
+    let map = sourcemap::SourceMap::from_slice(output.map.unwrap().as_bytes()).unwrap();
+    let src_line = |pattern: &str| {
+      let (line, code) = output
+        .code
+        .lines()
+        .enumerate()
+        .find(|(_, code)| code.contains(pattern))
+        .unwrap();
+      let col = code.len() - code.trim_start().len();
+      map
+        .lookup_token(line as u32, col as u32)
+        .unwrap()
+        .get_src_line()
+    };
+    // The generated registration and prologue point to the worklet, and the relocated body keeps
+    // its own lines.
+    assert_eq!(src_line("registerWorkletInternal"), 1);
+    assert_eq!(src_line("this[\"_c\"]"), 1);
+    assert_eq!(src_line("e.currentTarget.setStyleProperty"), 3);
+  }
+
+  #[test]
+  fn test_worklet_content_addressed_hash() {
+    use super::*;
+
+    let hashes = |filename: &str, code: &str| {
+      let options = TransformNodiffOptions {
+        filename: filename.into(),
+        worklet: BoolOr::Value(WorkletVisitorConfig {
+          filename: filename.into(),
+          content_addressed_hash: Some(true),
+          ..Default::default()
+        }),
+        ..Default::default()
+      };
+      let TransformNodiffDualOutput { lepus, js } =
+        transform_react_lynx_dual_inner(&Globals::new(), code.into(), options);
+      assert!(lepus.errors.is_empty());
+      assert!(js.errors.is_empty());
+      let hashes = |manifest: TransformNodiffManifest| {
+        manifest
+          .worklets
+          .into_iter()
+          .map(|worklet| worklet.hash)
+          .collect::<Vec<_>>()
+      };
+      let lepus = hashes(lepus.manifest.unwrap());
+      assert_eq!(lepus, hashes(js.manifest.unwrap()));
+      lepus
+    };
+
+    let a = hashes(
+      "a.jsx",
+      r#"
+const onTap = (e) => { 'main thread'; e.currentTarget.setStyleProperty('color', color); };
+const onScroll = (e) => { 'main thread'; console.log(e.detail.scrollTop); };
+"#,
+    );
+    // Inserting a worklet does not change the others, and the identical worklets of different
+    // files share one hash.
+    let b = hashes(
+      "b.jsx",
+      r#"
+const onLoad = () => { 'main thread'; console.log('load'); };
+function handleTap(e) { 'main thread'; e.currentTarget.setStyleProperty('color', color); }
+const onScroll = (e) => { 'main thread'; console.log(e.detail.scrollTop); };
+"#,
+    );
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 3);
+    assert_ne!(a[0], a[1]);
+    assert_eq!(a[0], b[1]);
+    assert_eq!(a[1], b[2]);
+
+    // The captured identifiers are a part of the hash.
+    let c = hashes(
+      "c.jsx",
+      "const onTap = (e) => { 'main thread'; e.currentTarget.setStyleProperty('color', colour); };",
+    );
+    assert_ne!(a[0], c[0]);
+  }
+
+  #[test]
+  fn test_invalid_worklet_directive() {
+    use super::*;
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"
+export function App() {
+  function onScroll(e) {
+    'use animation';
+    console.log(e);
+  }
+  return <view main-thread:bindscroll={onScroll} />;
+}
+"#
+      .into(),
+      TransformNodiffOptions {
+        filename: "index.jsx".into(),
+        mode: Some(TransformMode::Test),
+        worklet: BoolOr::Value(WorkletVisitorConfig {
+          filename: "index.jsx".into(),
+          target: TransformTarget::LEPUS,
+          directives: Some(vec![WorkletDirective {
+            directive: "use animation".into(),
+            type_str: "animation".into(),
+            register: "register-animation".into(),
+            import_source: "@lynx-js/animation".into(),
+          }]),
+          ..Default::default()
+        }),
+        ..Default::default()
+      },
+    );
+    // The worklet is not registered with an invalid name.
+    assert!(!output.code.contains("register-animation"));
+    assert_eq!(
+      output
+        .errors
+        .iter()
+        .map(|error| (
+          error.id.as_deref().unwrap(),
+          error.location.as_ref().unwrap().line,
+          error.text.as_deref().unwrap(),
+        ))
+        .collect::<Vec<_>>(),
+      [(
+        diagnostic_ids::INVALID_WORKLET_DIRECTIVE,
+        Some(4),
+        "The `register` of the worklet directive \"use animation\" is not a valid identifier: `register-animation`",
+      )]
+    );
+  }
+
+  #[test]
+  fn test_async_worklet() {
+    use super::*;
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"
+export function App() {
+  const onTap = async (e) => {
+    'main thread';
+    const data = await runOnBackground(fetchData)(e.detail);
+    runOnBackground(log)(data);
+    runOnBackground(getSize)().then((size) => console.log(size));
+    const pending = runOnBackground(getSize)();
+    const { width } = runOnBackground(getSize)();
+    console.log(width, await pending, `${runOnBackground(getSize)()}`);
+    return runOnBackground(fetchData)();
+  };
+  function onScroll(e) {
+    'main thread';
+    return runOnBackground(fetchData)(e.detail).text;
+  }
+  function* onLoad() {
+    'main thread';
+    yield 1;
+  }
+  return <view main-thread:bindtap={onTap} main-thread:bindscroll={onScroll} main-thread:bindload={onLoad} />;
+}
+"#
+      .into(),
+      TransformNodiffOptions {
+        filename: "index.jsx".into(),
+        mode: Some(TransformMode::Test),
+        worklet: BoolOr::Value(WorkletVisitorConfig {
+          filename: "index.jsx".into(),
+          target: TransformTarget::LEPUS,
+          ..Default::default()
+        }),
+        ..Default::default()
+      },
+    );
+    assert!(output.errors.is_empty());
+    // The `async` function is kept as it is.
+    assert!(output.code.contains("async function(e)"));
+    assert_eq!(
+      output
+        .warnings
+        .iter()
+        .map(|warning| (
+          warning.id.as_deref().unwrap(),
+          warning.location.as_ref().unwrap().line,
+          warning.notes.as_ref().unwrap()[0].text.as_deref().unwrap(),
+        ))
+        .collect::<Vec<_>>(),
+      [
+        (
+          diagnostic_ids::UNAWAITED_RUN_ON_BACKGROUND,
+          Some(9),
+          "`await` the Promise",
+        ),
+        (
+          diagnostic_ids::UNAWAITED_RUN_ON_BACKGROUND,
+          Some(10),
+          "`await` the Promise",
+        ),
+        (
+          diagnostic_ids::UNAWAITED_RUN_ON_BACKGROUND,
+          Some(15),
+          "make the main thread function `async` and `await` the Promise",
+        ),
+        (
+          diagnostic_ids::GENERATOR_WORKLET,
+          Some(17),
+          "use an `async` function and `await` the values of the background thread instead",
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_unserializable_capture() {
+    use super::*;
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"
+import { useRef, useState } from '@lynx-js/react';
+class Store {}
+export function App() {
+  const store = new Store();
+  const ref = useRef(null);
+  const [count, setCount] = useState(0);
+  const data = fetch('/data');
+  const log = () => console.log(count);
+  const onTap = () => {
+    'main thread';
+    store.name;
+    store;
+    ref;
+    setCount(count + 1);
+    data.then;
+    runOnBackground(log)();
+    // Another main thread function is fine.
+    other();
+  };
+  return <view main-thread:bindtap={onTap} />;
+}
+function other() {
+  'main thread';
+}
+"#
+      .into(),
+      TransformNodiffOptions {
+        filename: "index.jsx".into(),
+        mode: Some(TransformMode::Test),
+        worklet: BoolOr::Value(WorkletVisitorConfig {
+          filename: "index.jsx".into(),
+          target: TransformTarget::LEPUS,
+          ..Default::default()
+        }),
+        ..Default::default()
+      },
+    );
+    assert!(output.errors.is_empty());
+    assert_eq!(
+      output
+        .warnings
+        .iter()
+        .map(|warning| (
+          warning.id.as_deref().unwrap(),
+          warning.text.as_deref().unwrap(),
+          warning.location.as_ref().unwrap().line,
+          warning.notes.as_ref().unwrap()[0].text.as_deref().unwrap(),
+          warning.notes.as_ref().unwrap()[0].location.as_ref().unwrap().line,
+        ))
+        .collect::<Vec<_>>(),
+      [
+        (
+          diagnostic_ids::UNSERIALIZABLE_CAPTURE,
+          "`store` is captured by a main thread function, but it is an instance of `Store` which can not be sent to the main thread",
+          Some(13),
+          "`store` is declared here",
+          Some(5),
+        ),
+        (
+          diagnostic_ids::UNSERIALIZABLE_CAPTURE,
+          "`ref` is captured by a main thread function, but it is a React ref object which can not be sent to the main thread",
+          Some(14),
+          "`ref` is declared here",
+          Some(6),
+        ),
+        (
+          diagnostic_ids::UNSERIALIZABLE_CAPTURE,
+          "`setCount` is captured by a main thread function, but it is a background function which can not be sent to the main thread",
+          Some(15),
+          "`setCount` is declared here",
+          Some(7),
+        ),
+        (
+          diagnostic_ids::UNSERIALIZABLE_CAPTURE,
+          "`data` is captured by a main thread function, but it is a Promise which can not be sent to the main thread",
+          Some(16),
+          "`data` is declared here",
+          Some(8),
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_snapshot_uid_development() {
+    use super::*;
+
+    let uids = |code: &str, entry_name: Option<&str>| {
+      let output = transform_react_lynx_inner(
+        &Globals::new(),
+        code.into(),
+        TransformNodiffOptions {
+          filename: "index.jsx".into(),
+          mode: Some(TransformMode::Development),
+          snapshot: Some(BoolOr::Value(JSXTransformerConfig {
+            filename: "index.jsx".into(),
+            entry_name: entry_name.map(Into::into),
+            ..Default::default()
+          })),
+          ..Default::default()
+        },
+      );
+      assert!(output.errors.is_empty());
+      output
+        .manifest
+        .unwrap()
+        .snapshots
+        .into_iter()
+        .map(|snapshot| snapshot.uid)
+        .collect::<Vec<_>>()
+    };
+
+    let before = uids(
+      r#"
+export function App({ list }) {
+  return <view>{list.map(item => <text>{item}</text>)}</view>;
+}
+export const Footer = () => <view><text>footer</text></view>;
+"#,
+      None,
+    );
+    // The uids are named by the component and the path of the JSX.
+    assert_eq!(before.len(), 3);
+    assert!(before[0].contains("_App_0_0_"), "{}", before[0]);
+    assert!(before[1].contains("_App_0_"), "{}", before[1]);
+    assert!(before[2].contains("_Footer_0_"), "{}", before[2]);
+
+    let after = uids(
+      r#"
+// An unrelated edit.
+const unused = 1;
+export function App({ list }) {
+  return <view>{list.map(item => <text>{item}</text>)}</view>;
+}
+export const Footer = () => <view><text>changed</text></view>;
+"#,
+      None,
+    );
+    // Only the snapshot whose structure changed is renamed.
+    assert_eq!(before[0], after[0]);
+    assert_eq!(before[1], after[1]);
+    assert_ne!(before[2], after[2]);
+
+    let entry = uids("export const App = () => <view />;", Some("entry"));
+    assert_ne!(entry, uids("export const App = () => <view />;", None));
+  }
+
+  #[test]
+  fn test_element_registry() {
+    use super::*;
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"
+export const App = ({ provider }) => (
+  <view>
+    <map provider={provider}>
+      <view />
+    </map>
+    <rich-text>Hello, {provider}</rich-text>
+    <rich-text><text>Hello</text></rich-text>
+  </view>
+);
+"#
+      .into(),
+      TransformNodiffOptions {
+        filename: "index.jsx".into(),
+        mode: Some(TransformMode::Test),
+        snapshot: Some(BoolOr::Value(JSXTransformerConfig {
+          elements: Some(HashMap::from([
+            (
+              "map".into(),
+              ElementConfig {
+                creator: "__CreateMap".into(),
+                creation_attributes: Some(vec!["provider".into()]),
+                children: Some(ElementChildren::None),
+              },
+            ),
+            (
+              "rich-text".into(),
+              ElementConfig {
+                creator: "__CreateRichText".into(),
+                children: Some(ElementChildren::Text),
+                ..Default::default()
+              },
+            ),
+          ])),
+          ..Default::default()
+        })),
+        ..Default::default()
+      },
+    );
+
+    fn ids(messages: &[PartialMessage]) -> Vec<&str> {
+      messages
+        .iter()
+        .map(|message| message.id.as_deref().unwrap())
+        .collect()
+    }
+    assert_eq!(
+      ids(&output.warnings),
+      [diagnostic_ids::ELEMENT_CREATION_ATTRIBUTE]
+    );
+    assert_eq!(
+      ids(&output.errors),
+      [
+        diagnostic_ids::ELEMENT_CHILDREN,
+        diagnostic_ids::ELEMENT_CHILDREN
+      ]
+    );
+    assert_eq!(
+      output.errors[0].text.as_deref(),
+      Some("`<map>` can not have children.")
+    );
+    assert_eq!(
+      output.errors[1].text.as_deref(),
+      Some("`<rich-text>` can only have text children.")
+    );
+
+    // The dynamic creation attribute is set after the creation.
+    assert!(output.code.contains("__CreateMap(pageId)"));
+    assert!(output
+      .code
+      .contains("__SetAttribute(ctx.__elements[1], \"provider\""));
+  }
+
+  #[test]
+  fn test_pragmas() {
+    use super::*;
+
+    let options = TransformNodiffOptions {
+      filename: "index.jsx".into(),
+      mode: Some(TransformMode::Test),
+      ..Default::default()
+    };
+
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"/**
+ * @jsxImportSource @lynx-js/react/compat
+ * @jsxPreserve
+ * @jsxCSSId 100
+ */
+export const App = () => <view />;
+"#
+      .into(),
+      options.clone(),
+    );
+    assert!(output.warnings.is_empty());
+    assert!(output
+      .code
+      .contains(r#"import * as ReactLynx from "@lynx-js/react/compat";"#));
+    assert!(output.code.contains("<__snapshot_"));
+    assert!(output.code.contains(", 100, globDynamicComponentEntry)"));
+
+    // Invalid values are reported instead of panicking.
+    let output = transform_react_lynx_inner(
+      &Globals::new(),
+      r#"/** @jsxCSSId foo @lynxTarget MIXED @jsxPreserve true */
+export const App = () => <view />;
+"#
+      .into(),
+      options.clone(),
+    );
+    assert!(output.errors.is_empty());
+    assert_eq!(
+      output
+        .warnings
+        .iter()
+        .map(|warning| (
+          warning.id.as_deref().unwrap(),
+          warning.text.as_deref().unwrap()
+        ))
+        .collect::<Vec<_>>(),
+      [
+        (
+          diagnostic_ids::INVALID_PRAGMA,
+          "Invalid `@jsxCSSId foo`, expected a number. The pragma is ignored."
+        ),
+        (
+          diagnostic_ids::INVALID_PRAGMA,
+          "Invalid `@lynxTarget MIXED`, expected `LEPUS` or `JS`. The pragma is ignored."
+        ),
+        (
+          diagnostic_ids::INVALID_PRAGMA,
+          "Invalid `@jsxPreserve true`, expected no value. The pragma is ignored."
+        ),
+      ]
+    );
+
+    // `@lynxTarget` eliminates the file from the other thread of a dual transform.
+    let output = transform_react_lynx_dual_inner(
+      &Globals::new(),
+      r#"/** @lynxTarget JS */
+import { useState } from '@lynx-js/react';
+export * from './utils';
+export { default as Button, type Props } from './Button';
+export const App = () => <view />;
+export default function Page() {
+  const [count] = useState(0);
+  return <App />;
+}
+"#
+      .into(),
+      options.clone(),
+    );
+    // The main thread only links against the exports.
+    assert_eq!(
+      output.lepus.code,
+      r#"/** @lynxTarget JS */ const stub = void 0;
+export { stub as Button, stub as App, stub as default };
+export * from './utils';
+"#
+    );
+    assert!(output.js.code.contains("_test_1\", null, null"));
+    assert!(output.js.code.contains("useState(0)"));
+
+    let output = transform_react_lynx_dual_inner(
+      &Globals::new(),
+      r#"/** @lynxTarget LEPUS */
+export const App = () => <view />;
+"#
+      .into(),
+      options,
+    );
+    assert!(output.lepus.code.contains("__CreateView(pageId)"));
+    assert!(!output.js.code.contains("createSnapshot"));
+    assert!(output.js.code.contains("export { stub as App };"));
+  }
+
+  #[test]
+  fn test_options_deserialize() {
+    use super::*;
+    use serde_json::json;
+
+    let options: TransformNodiffOptions = serde_json::from_value(json!({
+      "mode": "development",
+      "filename": "index.jsx",
+      "sourcemap": true,
+      "isModule": "unknown",
+      "syntaxConfig": r#"{"syntax":"ecmascript","jsx":true}"#,
+      "cssScope": false,
+      "snapshot": { "target": "JS", "filename": "index.jsx" },
+      "defineDCE": { "define": { "__LEPUS__": "false" } },
+      "diagnostics": { "react-lynx-extract-css": "off" },
+      "allWarningsAsErrors": true,
+      "inject": { "inject": { "foo": ["importNamed", "pkg", "foo"] } },
+    }))
+    .unwrap();
+
+    assert_eq!(options.mode, Some(TransformMode::Development));
+    assert!(matches!(options.sourcemap, BoolOr::Bool(true)));
+    assert_eq!(
+      options.is_module.map(IsModule::from),
+      Some(IsModule::Unknown)
+    );
+    assert!(matches!(
+      options.syntax_config.map(Syntax::from),
+      Some(Syntax::Es(_))
+    ));
+    assert!(matches!(options.css_scope, BoolOr::Bool(false)));
+    assert_eq!(
+      options.diagnostics.unwrap()["react-lynx-extract-css"],
+      DiagnosticSeverity::Off
+    );
+    assert_eq!(options.all_warnings_as_errors, Some(true));
+    match options.snapshot {
+      Some(BoolOr::Value(snapshot)) => {
+        assert_eq!(snapshot.target, target::TransformTarget::JS);
+        // Missing fields fall back to the default value.
+        assert_eq!(snapshot.runtime_pkg, "@lynx-js/react");
+      }
+      _ => panic!("snapshot should be an object"),
+    }
+    match options.define_dce {
+      BoolOr::Value(define_dce) => assert_eq!(define_dce.define["__LEPUS__"], "false"),
+      _ => panic!("defineDCE should be an object"),
+    }
+    match options.inject {
+      Some(BoolOr::Value(inject)) => assert_eq!(
+        inject.inject["foo"],
+        swc_plugin_inject::InjectAs::ImportNamed("pkg".into(), "foo".into())
+      ),
+      _ => panic!("inject should be an object"),
+    }
+    // Missing fields fall back to `TransformNodiffOptions::default()`.
+    assert!(matches!(options.dynamic_import, Some(BoolOr::Value(_))));
+
+    assert!(serde_json::from_value::<TransformNodiffOptions>(json!({ "snapshot": 1 })).is_err());
+  }
 }
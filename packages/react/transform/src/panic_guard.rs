@@ -0,0 +1,136 @@
+use std::{
+  any::Any,
+  cell::Cell,
+  panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+
+use swc_core::{
+  common::{errors::HANDLER, Span, Spanned, DUMMY_SP},
+  ecma::ast::{Pass, Program},
+};
+
+thread_local! {
+  /// The span of the node that the running pass is working on.
+  static CURRENT_SPAN: Cell<Span> = const { Cell::new(DUMMY_SP) };
+}
+
+/// Record `span` as the location to report if the running pass panics.
+pub fn record_span(span: Span) {
+  if !span.is_dummy() {
+    CURRENT_SPAN.with(|current| current.set(span));
+  }
+}
+
+/// A pass that reports the panics of `pass` as errors instead of unwinding.
+///
+/// The error is emitted to the `HANDLER` with the name of the pass and the span recorded by
+/// [`record_span`], or the span of the whole program if nothing is recorded.
+pub struct PanicGuard<P> {
+  name: &'static str,
+  pass: P,
+}
+
+pub fn panic_guard<P: Pass>(name: &'static str, pass: P) -> PanicGuard<P> {
+  PanicGuard { name, pass }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    message
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    message
+  } else {
+    "unknown panic"
+  }
+}
+
+impl<P: Pass> Pass for PanicGuard<P> {
+  fn process(&mut self, program: &mut Program) {
+    let previous = CURRENT_SPAN.with(|current| current.replace(program.span()));
+
+    let result = catch_unwind(AssertUnwindSafe(|| self.pass.process(program)));
+    let span = CURRENT_SPAN.with(|current| current.replace(previous));
+
+    if let Err(payload) = result {
+      if !HANDLER.is_set() {
+        resume_unwind(payload);
+      }
+      HANDLER.with(|handler| {
+        handler
+          .struct_span_err(
+            span,
+            &format!(
+              "Internal error in `{}`: {}",
+              self.name,
+              panic_message(payload.as_ref())
+            ),
+          )
+          .emit()
+      });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use swc_core::{
+    common::{
+      errors::{Handler, HANDLER},
+      sync::Lrc,
+      FileName, SourceMap, Spanned,
+    },
+    ecma::{
+      ast::{fn_pass, EsVersion, Pass, Program},
+      parser::{parse_file_as_program, Syntax},
+    },
+  };
+
+  use super::{panic_guard, record_span};
+  use crate::esbuild::EsbuildEmitter;
+
+  #[test]
+  fn test_panic_guard() {
+    let cm = Lrc::new(SourceMap::default());
+    let fm = cm.new_source_file(
+      FileName::Real("index.js".into()).into(),
+      "let a = 1;\nlet b = 2;".into(),
+    );
+    let mut program = parse_file_as_program(
+      &fm,
+      Syntax::default(),
+      EsVersion::latest(),
+      None,
+      &mut vec![],
+    )
+    .unwrap();
+
+    let (emitter, errors, _) = EsbuildEmitter::new("test".into(), Some(cm.clone()));
+    let handler = Handler::with_emitter(true, false, Box::new(emitter));
+
+    HANDLER.set(&handler, || {
+      panic_guard(
+        "panicking",
+        fn_pass(|program: &mut Program| {
+          let stmt = &program.as_script().unwrap().body[1];
+          record_span(stmt.span());
+          panic!("something is wrong");
+        }),
+      )
+      .process(&mut program);
+
+      // The following passes still run.
+      panic_guard("ok", fn_pass(|_: &mut Program| {})).process(&mut program);
+    });
+
+    let errors = errors.read().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+      errors[0].text.as_deref(),
+      Some("Internal error in `panicking`: something is wrong")
+    );
+    let location = errors[0].location.as_ref().unwrap();
+    assert_eq!(location.file.as_deref(), Some("index.js"));
+    assert_eq!(location.line, Some(2));
+    assert_eq!(location.line_text.as_deref(), Some("let b = 2;"));
+  }
+}
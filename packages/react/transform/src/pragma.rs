@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+
+use swc_core::{
+  common::{
+    comments::{CommentKind, Comments},
+    errors::HANDLER,
+    BytePos, Span, DUMMY_SP,
+  },
+  ecma::{ast::*, utils::find_pat_ids},
+};
+
+use crate::{
+  diagnostics::{ids, lint},
+  target::TransformTarget,
+};
+
+/// The value of `@jsxRuntime`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JsxRuntime {
+  Classic,
+  Automatic,
+}
+
+/// The per-file pragmas in the leading block comments of a module.
+///
+/// ```js
+/// /**
+///  * @jsxImportSource @lynx-js/react
+///  * @jsxRuntime automatic
+///  * @jsxPreserve
+///  * @jsxCSSId 100
+///  * @lynxTarget LEPUS
+///  */
+/// ```
+///
+/// The invalid values are reported with `react-lynx-invalid-pragma` and ignored.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Pragmas {
+  /// `@jsxImportSource <pkg>`, which is also the runtime package of the snapshots.
+  pub jsx_import_source: Option<String>,
+  /// `@jsxRuntime classic|automatic`.
+  pub jsx_runtime: Option<JsxRuntime>,
+  /// `@jsxPreserve`, keep the JSX after the snapshot transform.
+  pub jsx_preserve: bool,
+  /// `@jsxCSSId <number>`.
+  pub jsx_css_id: Option<f64>,
+  /// `@lynxTarget LEPUS|JS`, compile the file for one thread only.
+  pub lynx_target: Option<TransformTarget>,
+}
+
+impl Pragmas {
+  /// Parse the pragmas in the block comments leading `spans`, e.g.: the module and its items.
+  pub fn parse<C: Comments>(comments: &C, spans: impl IntoIterator<Item = Span>) -> Self {
+    let mut pragmas = Pragmas::default();
+    let mut visited = HashSet::<BytePos>::new();
+    for span in spans {
+      if !visited.insert(span.lo) {
+        continue;
+      }
+      let Some(leading) = comments.get_leading(span.lo) else {
+        continue;
+      };
+      for cmt in leading {
+        if cmt.kind != CommentKind::Block {
+          continue;
+        }
+        for line in cmt.text.lines() {
+          let line = line.trim().trim_start_matches('*');
+          let mut words = line.split_whitespace().peekable();
+          while let Some(pragma) = words.next() {
+            if !is_pragma(pragma) {
+              continue;
+            }
+            let value = words.next_if(|word| !is_pragma(word));
+            pragmas.apply(pragma, value, cmt.span);
+          }
+        }
+      }
+    }
+    pragmas
+  }
+
+  fn apply(&mut self, pragma: &str, value: Option<&str>, span: Span) {
+    let expected = match (pragma, value) {
+      ("@jsxImportSource", Some(source)) => {
+        self.jsx_import_source = Some(source.to_string());
+        return;
+      }
+      ("@jsxImportSource", None) => "a package name",
+      ("@jsxRuntime", Some("classic")) => {
+        self.jsx_runtime = Some(JsxRuntime::Classic);
+        return;
+      }
+      ("@jsxRuntime", Some("automatic")) => {
+        self.jsx_runtime = Some(JsxRuntime::Automatic);
+        return;
+      }
+      // Reported by the React transform, which also reads `@jsxRuntime`.
+      ("@jsxRuntime", _) => return,
+      ("@jsxPreserve", None) => {
+        self.jsx_preserve = true;
+        return;
+      }
+      ("@jsxPreserve", Some(_)) => "no value",
+      ("@jsxCSSId", Some(css_id)) => match css_id.parse::<f64>() {
+        Ok(css_id) if css_id.is_finite() => {
+          self.jsx_css_id = Some(css_id);
+          return;
+        }
+        _ => "a number",
+      },
+      ("@jsxCSSId", None) => "a number",
+      ("@lynxTarget", Some("LEPUS")) => {
+        self.lynx_target = Some(TransformTarget::LEPUS);
+        return;
+      }
+      ("@lynxTarget", Some("JS")) => {
+        self.lynx_target = Some(TransformTarget::JS);
+        return;
+      }
+      ("@lynxTarget", _) => "`LEPUS` or `JS`",
+      // The other pragmas, e.g.: `@jsx h`, are not ours.
+      _ => return,
+    };
+
+    HANDLER.with(|handler| {
+      handler
+        .struct_span_warn_with_code(
+          span,
+          &match value {
+            Some(value) => format!(
+              "Invalid `{} {}`, expected {}. The pragma is ignored.",
+              pragma, value, expected
+            ),
+            None => format!(
+              "Invalid `{}`, expected {}. The pragma is ignored.",
+              pragma, expected
+            ),
+          },
+          lint(ids::INVALID_PRAGMA),
+        )
+        .emit()
+    });
+  }
+}
+
+/// `@jsxCSSId` is a pragma, but `@lynx-js/react` is the value of `@jsxImportSource`.
+fn is_pragma(word: &str) -> bool {
+  word
+    .strip_prefix('@')
+    .is_some_and(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+/// Remove the code of a module which is `@lynxTarget` to the other thread.
+///
+/// Only the exports are left, bound to `undefined`, so that the imports of the module still link:
+///
+/// ```js
+/// const stub = void 0;
+/// export { stub as App, stub as default };
+/// ```
+///
+/// `export * from` is kept as it is, its names are unknown here.
+pub fn eliminate_module(program: &mut Program) {
+  let module = match program {
+    Program::Module(module) => module,
+    Program::Script(script) => {
+      script.body.clear();
+      return;
+    }
+  };
+
+  let mut exported = vec![];
+  let mut export_all = vec![];
+  for item in module.body.drain(..) {
+    let ModuleItem::ModuleDecl(decl) = item else {
+      continue;
+    };
+    match decl {
+      ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+        Decl::Class(ClassDecl { ident, .. }) | Decl::Fn(FnDecl { ident, .. }) => {
+          exported.push(ModuleExportName::Ident(ident))
+        }
+        Decl::Var(var) => exported.extend(
+          find_pat_ids::<_, Ident>(&var.decls)
+            .into_iter()
+            .map(ModuleExportName::Ident),
+        ),
+        // The types, which are already stripped.
+        _ => {}
+      },
+      ModuleDecl::ExportNamed(NamedExport {
+        specifiers,
+        type_only: false,
+        ..
+      }) => exported.extend(
+        specifiers
+          .into_iter()
+          .filter_map(|specifier| match specifier {
+            ExportSpecifier::Named(ExportNamedSpecifier {
+              orig,
+              exported,
+              is_type_only: false,
+              ..
+            }) => Some(exported.unwrap_or(orig)),
+            ExportSpecifier::Named(_) => None,
+            ExportSpecifier::Namespace(ExportNamespaceSpecifier { name, .. }) => Some(name),
+            ExportSpecifier::Default(ExportDefaultSpecifier { exported }) => {
+              Some(ModuleExportName::Ident(exported))
+            }
+          }),
+      ),
+      ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_) => exported.push(
+        ModuleExportName::Ident(Ident::new_no_ctxt("default".into(), DUMMY_SP)),
+      ),
+      ModuleDecl::ExportAll(export_all_decl) if !export_all_decl.type_only => export_all.push(
+        ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all_decl)),
+      ),
+      _ => {}
+    }
+  }
+
+  if !exported.is_empty() {
+    let stub = Ident::new_no_ctxt("stub".into(), DUMMY_SP);
+    module
+      .body
+      .push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+        kind: VarDeclKind::Const,
+        decls: vec![VarDeclarator {
+          span: DUMMY_SP,
+          name: Pat::Ident(stub.clone().into()),
+          init: Some(Expr::undefined(DUMMY_SP)),
+          definite: false,
+        }],
+        ..Default::default()
+      })))));
+    module
+      .body
+      .push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+        NamedExport {
+          span: DUMMY_SP,
+          specifiers: exported
+            .into_iter()
+            .map(|exported| {
+              ExportSpecifier::Named(ExportNamedSpecifier {
+                span: DUMMY_SP,
+                orig: ModuleExportName::Ident(stub.clone()),
+                exported: Some(exported),
+                is_type_only: false,
+              })
+            })
+            .collect(),
+          src: None,
+          type_only: false,
+          with: None,
+        },
+      )));
+  }
+  module.body.extend(export_all);
+}
@@ -0,0 +1,317 @@
+//! The ReactLynx transform as a SWC Wasm plugin.
+//!
+//! Build it with:
+//!
+//! ```sh
+//! pnpm build:swc-plugin
+//! ```
+//!
+//! And use it in the `jsc.experimental.plugins` of `@swc/core`:
+//!
+//! ```json
+//! ["./target/wasm32-wasip1/release/react_transform.wasm", { "snapshot": { "target": "LEPUS" }, "worklet": false }]
+//! ```
+//!
+//! Only the passes that are not covered by SWC itself are included. The JSX of components are
+//! left for the builtin `jsc.transform.react` with `importSource: "@lynx-js/react"`.
+
+use serde::Deserialize;
+use swc_core::{
+  common::{comments::Comments, errors::SourceMapperDyn, pass::Optional, sync::Lrc, Mark},
+  ecma::{ast::Pass, visit::visit_mut_pass},
+};
+#[cfg(feature = "swc_plugin")]
+use swc_core::{
+  common::{errors::SourceMapper, Spanned},
+  ecma::{ast::Program, codegen::to_code},
+  plugin::{
+    metadata::{TransformPluginMetadataContextKind, TransformPluginProgramMetadata},
+    plugin_transform,
+  },
+};
+
+use crate::{
+  calc_hash, panic_guard::panic_guard, BoolOr, CSSScopeVisitor, CSSScopeVisitorConfig,
+  DirectiveDCEVisitor, DirectiveDCEVisitorConfig, JSXTransformer, JSXTransformerConfig,
+  TransformMode, WorkletVisitor, WorkletVisitorConfig,
+};
+
+/// The plugin config, it is a subset of `TransformNodiffOptions`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SwcPluginConfig {
+  pub mode: TransformMode,
+  pub css_scope: BoolOr<CSSScopeVisitorConfig>,
+  pub snapshot: BoolOr<JSXTransformerConfig>,
+  #[serde(rename = "directiveDCE")]
+  pub directive_dce: BoolOr<DirectiveDCEVisitorConfig>,
+  pub worklet: BoolOr<WorkletVisitorConfig>,
+}
+
+impl Default for SwcPluginConfig {
+  fn default() -> Self {
+    Self {
+      mode: TransformMode::Production,
+      css_scope: BoolOr::Value(Default::default()),
+      snapshot: BoolOr::Bool(true),
+      directive_dce: BoolOr::Bool(false),
+      worklet: BoolOr::Bool(false),
+    }
+  }
+}
+
+fn resolve_config<T: Default>(config: BoolOr<T>) -> (T, bool) {
+  match config {
+    BoolOr::Bool(enabled) => (T::default(), enabled),
+    BoolOr::Value(config) => (config, true),
+  }
+}
+
+/// The passes of the plugin, in the same order as `transformReactLynx`.
+///
+/// `code` is the source of the file, the snapshot uids and the worklet hashes are derived from it
+/// as `transformReactLynx` does, so that they change with the file. `cm` resolves the source
+/// locations of the elements.
+pub fn swc_plugin_pass<C>(
+  config: SwcPluginConfig,
+  filename: String,
+  code: &str,
+  cm: Lrc<SourceMapperDyn>,
+  comments: Option<C>,
+  unresolved_mark: Mark,
+) -> impl Pass
+where
+  C: Comments + Clone,
+{
+  let content_hash = match config.mode {
+    TransformMode::Test => "test".into(),
+    _ => calc_hash(code),
+  };
+  let top_level_mark = Mark::new();
+
+  let (mut worklet_config, worklet_enabled) = resolve_config(config.worklet);
+  if worklet_config.filename.is_empty() {
+    worklet_config.filename = filename.clone();
+  }
+  let worklet_plugin = Optional::new(
+    visit_mut_pass(
+      WorkletVisitor::new(config.mode, worklet_config).with_content_hash(content_hash.clone()),
+    ),
+    worklet_enabled,
+  );
+
+  let (mut css_scope_config, css_scope_enabled) = resolve_config(config.css_scope);
+  css_scope_config.filename = filename.clone();
+  let css_scope_plugin = Optional::new(
+    visit_mut_pass(CSSScopeVisitor::new(css_scope_config, comments.clone())),
+    css_scope_enabled,
+  );
+
+  let (mut snapshot_config, snapshot_enabled) = resolve_config(config.snapshot);
+  if snapshot_config.filename.is_empty() {
+    snapshot_config.filename = filename;
+  }
+  let snapshot_plugin = Optional::new(
+    visit_mut_pass(
+      JSXTransformer::new(
+        snapshot_config,
+        cm,
+        comments,
+        top_level_mark,
+        unresolved_mark,
+        config.mode,
+      )
+      .with_content_hash(content_hash),
+    ),
+    snapshot_enabled,
+  );
+
+  let (directive_dce_config, directive_dce_enabled) = resolve_config(config.directive_dce);
+  let directive_dce_plugin = Optional::new(
+    visit_mut_pass(DirectiveDCEVisitor::new(directive_dce_config)),
+    directive_dce_enabled,
+  );
+
+  (
+    panic_guard("worklet", worklet_plugin),
+    panic_guard("cssScope", css_scope_plugin),
+    panic_guard("snapshot", snapshot_plugin),
+    panic_guard("directiveDCE", directive_dce_plugin),
+  )
+}
+
+#[cfg(feature = "swc_plugin")]
+#[plugin_transform]
+pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+  let config = match metadata.get_transform_plugin_config() {
+    Some(config) => serde_json::from_str::<SwcPluginConfig>(&config)
+      .unwrap_or_else(|err| panic!("Invalid config of ReactLynx swc plugin: {}", err)),
+    None => SwcPluginConfig::default(),
+  };
+  let filename = metadata
+    .get_context(&TransformPluginMetadataContextKind::Filename)
+    .unwrap_or_else(|| "index.js".into());
+  let code = metadata
+    .source_map
+    .span_to_snippet(program.span())
+    .unwrap_or_else(|_| to_code(&program));
+
+  program.apply(swc_plugin_pass(
+    config,
+    filename,
+    &code,
+    Lrc::new(metadata.source_map.clone()),
+    metadata.comments,
+    metadata.unresolved_mark,
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use swc_core::{
+    common::{comments::SingleThreadedComments, Mark},
+    ecma::{
+      parser::{EsSyntax, Syntax},
+      transforms::{
+        base::resolver,
+        testing::{test, Tester},
+      },
+    },
+  };
+
+  use crate::{
+    swc_plugin::{swc_plugin_pass, SwcPluginConfig},
+    BoolOr, DirectiveDCEVisitorConfig, JSXTransformerConfig, TransformMode, TransformTarget,
+    WorkletVisitorConfig,
+  };
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        swc_plugin_pass::<&SingleThreadedComments>(
+          SwcPluginConfig {
+            mode: TransformMode::Test,
+            snapshot: BoolOr::Value(JSXTransformerConfig {
+              preserve_jsx: true,
+              target: TransformTarget::LEPUS,
+              ..Default::default()
+            }),
+            directive_dce: BoolOr::Value(DirectiveDCEVisitorConfig {
+              target: TransformTarget::LEPUS,
+            }),
+            worklet: BoolOr::Value(WorkletVisitorConfig {
+              target: TransformTarget::LEPUS,
+              ..Default::default()
+            }),
+            ..Default::default()
+          },
+          "index.jsx".into(),
+          "",
+          t.cm.clone(),
+          None,
+          unresolved_mark,
+        ),
+      )
+    },
+    should_run_snapshot_worklet_and_directive_dce,
+    r#"
+    import './index.css';
+    function App() {
+      function onTap(e) {
+        'main thread';
+        console.log(e);
+      }
+      function onClick() {
+        'background only';
+        console.log('click');
+      }
+      return <view main-thread:bindtap={onTap} bindtap={onClick}><text>Hello</text></view>;
+    }
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        swc_plugin_pass::<&SingleThreadedComments>(
+          SwcPluginConfig {
+            mode: TransformMode::Development,
+            snapshot: BoolOr::Value(JSXTransformerConfig {
+              preserve_jsx: true,
+              target: TransformTarget::LEPUS,
+              source_locations: Some(true),
+              ..Default::default()
+            }),
+            ..Default::default()
+          },
+          "index.jsx".into(),
+          "",
+          t.cm.clone(),
+          None,
+          unresolved_mark,
+        ),
+      )
+    },
+    should_attach_source_locations_with_plugin_source_map,
+    r#"
+    function App() {
+      return (
+        <view>
+          <text>Hello</text>
+        </view>
+      );
+    }
+    "#
+  );
+
+  #[test]
+  fn test_content_hash() {
+    let snapshot_uid = |code: &str| {
+      let output = Tester::run(|tester| {
+        let program = tester.apply_transform(
+          swc_plugin_pass::<&SingleThreadedComments>(
+            SwcPluginConfig::default(),
+            "index.jsx".into(),
+            code,
+            tester.cm.clone(),
+            None,
+            Mark::new(),
+          ),
+          "index.jsx",
+          Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+          }),
+          Some(true),
+          code,
+        )?;
+        Ok(tester.print(&program, &Default::default()))
+      });
+      let start = output.find("__snapshot_").unwrap();
+      output[start..].split('"').next().unwrap().to_string()
+    };
+
+    let uid = snapshot_uid("<view />;");
+    assert_eq!(uid, snapshot_uid("<view />;"));
+    // The uid changes with the content of the file, not only its name.
+    assert_ne!(uid, snapshot_uid("<view />;\nconsole.log();"));
+  }
+}
@@ -1,15 +1,17 @@
 use std::vec;
 
 use convert_case::{Case, Casing};
-use napi::Either;
-use napi_derive::napi;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
 use swc_core::common::comments::Comments;
 use swc_core::common::util::take::Take;
 use swc_core::common::Span;
 use swc_core::{
-  common::{errors::HANDLER, DUMMY_SP},
+  common::{
+    errors::{Applicability, HANDLER},
+    DUMMY_SP,
+  },
   ecma::{
     ast::*,
     utils::{prepend_stmt, private_ident},
@@ -18,13 +20,21 @@ use swc_core::{
   quote,
 };
 
+use crate::diagnostics::{ids, lint};
+use crate::panic_guard::record_span;
 use crate::target::TransformTarget;
+use crate::BoolOr;
 
 mod is_component_class;
 mod simplify_ctor_like_react_lynx_2;
 
 type Stack<T> = Vec<T>;
 
+static EVENT_RE: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"^(global-bind|bind|catch|capture-bind|capture-catch)([A-Za-z]+)$").unwrap()
+});
+static DATASET_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^data-([A-Za-z]+)$").unwrap());
+
 // Note: Should sync with Lynx/tasm/component_attributes.cc
 static COMPONENT_ATTRIBUTES: Lazy<Vec<&str>> = Lazy::new(|| {
   vec![
@@ -57,24 +67,27 @@ static COMPONENT_ATTRIBUTES: Lazy<Vec<&str>> = Lazy::new(|| {
 });
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DarkModeConfig {
   /// @public
   pub theme_expr: String,
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddComponentElementConfig {
   /// @public
   pub compiler_only: bool,
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct CompatVisitorConfig {
   /// @internal
-  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED'")]
+  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED' | 'SSR'")]
   pub target: TransformTarget,
   /// @public
   pub components_pkg: Vec<String>,
@@ -85,7 +98,8 @@ pub struct CompatVisitorConfig {
   /// @public
   pub additional_component_attributes: Vec<String>,
   /// @public
-  pub add_component_element: Either<bool, AddComponentElementConfig>,
+  #[napi(ts_type = "boolean | AddComponentElementConfig")]
+  pub add_component_element: BoolOr<AddComponentElementConfig>,
   /// @public
   /// @deprecated
   pub simplify_ctor_like_react_lynx_2: bool,
@@ -97,7 +111,8 @@ pub struct CompatVisitorConfig {
   pub disable_deprecated_warning: bool,
   /// @public
   /// @deprecated
-  pub dark_mode: Option<Either<bool, DarkModeConfig>>,
+  #[napi(ts_type = "boolean | DarkModeConfig")]
+  pub dark_mode: Option<BoolOr<DarkModeConfig>>,
 }
 
 impl Default for CompatVisitorConfig {
@@ -108,7 +123,7 @@ impl Default for CompatVisitorConfig {
       old_runtime_pkg: vec!["@lynx-js/react-runtime".into()],
       new_runtime_pkg: "@lynx-js/react".into(),
       additional_component_attributes: vec![],
-      add_component_element: Either::A(false),
+      add_component_element: BoolOr::Bool(false),
       simplify_ctor_like_react_lynx_2: false,
       remove_component_attr_regex: None,
       disable_deprecated_warning: false,
@@ -259,9 +274,10 @@ where
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
           handler
-            .struct_span_warn(
+            .struct_span_warn_with_code(
               n.span,
               format!("DEPRECATED: old package \"{}\" is removed", n.src.value).as_str(),
+              lint(ids::DEPRECATED_COMPONENTS_PKG),
             )
             .emit()
         });
@@ -274,13 +290,20 @@ where
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
           handler
-            .struct_span_warn(
+            .struct_span_warn_with_code(
               n.span,
               format!(
                 "DEPRECATED: old runtime package \"{}\" is changed to \"{}\"",
                 n.src.value, self.opts.new_runtime_pkg
               )
               .as_str(),
+              lint(ids::DEPRECATED_RUNTIME_PKG),
+            )
+            .span_suggestion_with_applicability(
+              n.src.span,
+              "use the new runtime package",
+              format!("\"{}\"", self.opts.new_runtime_pkg),
+              Applicability::MachineApplicable,
             )
             .emit()
         });
@@ -308,7 +331,7 @@ where
   fn visit_mut_expr(&mut self, n: &mut Expr) {
     match n {
       Expr::JSXElement(_) => match self.opts.add_component_element {
-        Either::A(true) => {
+        BoolOr::Bool(true) => {
           let state = (vec![], JSXElement::dummy(), false, false);
           self.add_component_element_state.push(state);
           n.visit_mut_children_with(self);
@@ -338,7 +361,7 @@ where
   fn visit_mut_jsx_element_child(&mut self, child: &mut JSXElementChild) {
     match child {
       JSXElementChild::JSXElement(_) => match self.opts.add_component_element {
-        Either::A(true) => {
+        BoolOr::Bool(true) => {
           let state = (vec![], JSXElement::dummy(), false, false);
           self.add_component_element_state.push(state);
           child.visit_mut_children_with(self);
@@ -369,6 +392,7 @@ where
   }
 
   fn visit_mut_jsx_element(&mut self, n: &mut JSXElement) {
+    record_span(n.span);
     let is_component_is = match &n {
       JSXElement {
         opening: JSXOpeningElement { name, attrs, .. },
@@ -421,7 +445,7 @@ where
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
                     handler
-                        .struct_span_warn(n.span, format!("DEPRECATED: syntax `<component is=? />` is deprecated, use `lazy` and `loadLazyBundle` exported from \"{}\" instead.", self.opts.new_runtime_pkg).as_str())
+                        .struct_span_warn_with_code(n.span, format!("DEPRECATED: syntax `<component is=? />` is deprecated, use `lazy` and `loadLazyBundle` exported from \"{}\" instead.", self.opts.new_runtime_pkg).as_str(), lint(ids::DEPRECATED_COMPONENT_IS))
                         .emit()
                 });
       }
@@ -478,7 +502,7 @@ where
       // ignore_this_jsx, default false,
       // if some attr is removeComponentElement={true}, ignore_this_jsx = true
       // if some attr is JSXSpread, ignore_this_jsx = true
-      if !matches!(self.opts.add_component_element, Either::A(false)) {
+      if !matches!(self.opts.add_component_element, BoolOr::Bool(false)) {
         // we use `iter().rev()` because JSXAttr will override previous SpreadElement
         ignore_this_jsx = n.opening.attrs.iter().rev().any(|attr| match attr {
           JSXAttrOrSpread::JSXAttr(attr) => match (&attr.name, &attr.value) {
@@ -503,15 +527,16 @@ where
           JSXAttrOrSpread::SpreadElement(spread) => {
             if matches!(
               self.opts.add_component_element,
-              Either::B(AddComponentElementConfig {
+              BoolOr::Value(AddComponentElementConfig {
                 compiler_only: true
               })
             ) {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(
+                  .struct_span_warn_with_code(
                     spread.dot3_token,
                     "addComponentElement: component with JSXSpread is ignored to avoid badcase, you can switch addComponentElement.compilerOnly to false to enable JSXSpread support",
+                    lint(ids::COMPONENT_ELEMENT_SPREAD),
                   )
                   .emit()
               });
@@ -522,7 +547,7 @@ where
           }
         });
 
-        if matches!(self.opts.add_component_element, Either::A(true)) && has_spread {
+        if matches!(self.opts.add_component_element, BoolOr::Bool(true)) && has_spread {
           ignore_this_jsx = false;
         }
       }
@@ -553,9 +578,10 @@ where
           if ident_str == "item-key" {
             HANDLER.with(|handler| {
               handler
-                .struct_span_warn(
+                .struct_span_warn_with_code(
                   ident.span,
                   "BROKEN: \"item-key\" in component props takes no effect, this may indicate that your code is not fully migrated",
+                  lint(ids::BROKEN_ITEM_KEY),
                 )
                 .emit()
             });
@@ -574,7 +600,7 @@ where
 
       let mut primitive_attrs = vec![];
 
-      if !matches!(self.opts.add_component_element, Either::A(false))
+      if !matches!(self.opts.add_component_element, BoolOr::Bool(false))
         && !ignore_this_jsx
         && !has_spread
       {
@@ -597,12 +623,7 @@ where
                 return false;
               }
 
-              let re1 =
-                Regex::new(r"^(global-bind|bind|catch|capture-bind|capture-catch)([A-Za-z]+)$")
-                  .unwrap();
-              let re2 = Regex::new(r"^data-([A-Za-z]+)$").unwrap();
-
-              if re1.is_match(ident_str.as_str()) || re2.is_match(ident_str.as_str()) {
+              if EVENT_RE.is_match(ident_str.as_str()) || DATASET_RE.is_match(ident_str.as_str()) {
                 primitive_attrs.push(JSXAttrOrSpread::JSXAttr(attr.clone()));
                 return false;
               }
@@ -623,10 +644,7 @@ where
               name,
               span: _,
             }) => {
-              let re1 =
-                Regex::new(r"^(global-bind|bind|catch|capture-bind|capture-catch)([A-Za-z]+)$")
-                  .unwrap();
-              if re1.is_match(name.sym.to_string().as_str()) {
+              if EVENT_RE.is_match(name.sym.to_string().as_str()) {
                 primitive_attrs.push(JSXAttrOrSpread::JSXAttr(attr.clone()));
                 return false;
               }
@@ -641,11 +659,11 @@ where
       n.visit_mut_children_with(self);
       self.is_target_jsx_element.pop();
 
-      if !matches!(self.opts.add_component_element, Either::A(false)) && !ignore_this_jsx {
+      if !matches!(self.opts.add_component_element, BoolOr::Bool(false)) && !ignore_this_jsx {
         // <C /> => <view><C/></view>
         if matches!(
           self.opts.add_component_element,
-          Either::B(AddComponentElementConfig {
+          BoolOr::Value(AddComponentElementConfig {
             compiler_only: true
           })
         ) {
@@ -711,17 +729,24 @@ where
       None
     }
 
-    let warning_transform_event_name = |old_name, new_name| {
+    let warning_transform_event_name = |old_name, new_name: &String, span| {
       if !self.opts.disable_deprecated_warning {
         HANDLER.with(|handler| {
           handler
-            .struct_span_warn(
+            .struct_span_warn_with_code(
               n.span,
               format!(
                 "DEPRECATED: old event props \"{}\" is changed to \"{}\"",
                 old_name, new_name
               )
               .as_str(),
+              lint(ids::DEPRECATED_EVENT_PROPS),
+            )
+            .span_suggestion_with_applicability(
+              span,
+              "use the new event props",
+              new_name.clone(),
+              Applicability::MachineApplicable,
             )
             .emit()
         });
@@ -732,13 +757,13 @@ where
       match &n.name {
         JSXAttrName::Ident(id) => {
           if let Some(new_name) = transform_event_name(id.sym.to_string().as_str()) {
-            warning_transform_event_name(&id.sym, &new_name);
+            warning_transform_event_name(&id.sym, &new_name, id.span);
             n.name = JSXAttrName::Ident(IdentName::new(new_name.into(), id.span));
           }
         }
         JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name, span }) => {
           if let Some(new_name) = transform_event_name(name.sym.to_string().as_str()) {
-            warning_transform_event_name(&name.sym, &new_name);
+            warning_transform_event_name(&name.sym, &new_name, name.span);
             n.name = JSXAttrName::JSXNamespacedName(JSXNamespacedName {
               ns: ns.clone(),
               name: IdentName::new(new_name.into(), name.span).into(),
@@ -755,7 +780,17 @@ where
           if !self.opts.disable_deprecated_warning {
             HANDLER.with(|handler| {
               handler
-                .struct_span_warn(id.span, "DEPRECATED: lynx-key is changed to key")
+                .struct_span_warn_with_code(
+                  id.span,
+                  "DEPRECATED: lynx-key is changed to key",
+                  lint(ids::DEPRECATED_LYNX_KEY),
+                )
+                .span_suggestion_with_applicability(
+                  id.span,
+                  "use `key`",
+                  "key".into(),
+                  Applicability::MachineApplicable,
+                )
                 .emit()
             });
           }
@@ -783,13 +818,20 @@ where
             if !self.opts.disable_deprecated_warning {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(
+                  .struct_span_warn_with_code(
                     id.span,
                     format!(
                       "DEPRECATED: old JSXElementName \"{}\" is changed to \"{}\"",
                       id.sym, new_id_str
                     )
                     .as_str(),
+                    lint(ids::DEPRECATED_ELEMENT_NAME),
+                  )
+                  .span_suggestion_with_applicability(
+                    id.span,
+                    "use the new element name",
+                    new_id_str.clone(),
+                    Applicability::MachineApplicable,
                   )
                   .emit()
               });
@@ -815,9 +857,10 @@ where
               if id.sym.to_string() == "stopPropagation" {
                 HANDLER.with(|handler| {
                   handler
-                    .struct_span_warn(
+                    .struct_span_warn_with_code(
                       n.span,
                       "BROKEN: e.stopPropagation() takes no effect and MUST be migrated in ReactLynx 3.0",
+                      lint(ids::BROKEN_STOP_PROPAGATION),
                     )
                     .emit()
                 });
@@ -831,9 +874,10 @@ where
                 "getNodeRef" | "getNodeRefFromRoot" | "createSelectorQuery" => {
                   HANDLER.with(|handler| {
                                             handler
-                                                .struct_span_warn(
+                                                .struct_span_warn_with_code(
                                                     n.span,
                                                     format!("BROKEN: {} on component instance is broken and MUST be migrated in ReactLynx 3.0, please use ref or lynx.createSelectorQuery instead.", id.sym.to_string()).as_str(),
+                                                    lint(ids::BROKEN_INSTANCE_SELECTOR),
                                                 )
                                                 .emit()
                                         });
@@ -841,9 +885,10 @@ where
                 "getElementById" => {
                   HANDLER.with(|handler| {
                                         handler
-                                            .struct_span_warn(
+                                            .struct_span_warn_with_code(
                                                 n.span,
                                                 format!("BROKEN: {} on component instance is broken and MUST be migrated in ReactLynx 3.0, please use ref or lynx.getElementById instead.", id.sym.to_string()).as_str(),
+                                                lint(ids::BROKEN_INSTANCE_SELECTOR),
                                             )
                                             .emit()
                                     });
@@ -955,9 +1000,10 @@ where
                         if ident.sym.to_string() == "config" {
                             HANDLER.with(|handler| {
                                 handler
-                                    .struct_span_warn(
+                                    .struct_span_warn_with_code(
                                         *span,
                                         "BROKEN: supporting for class property `config` is removed and MUST be migrated in ReactLynx 3.0, you should put your configs inside `pageConfig` in lynx.config.js",
+                                        lint(ids::BROKEN_CLASS_CONFIG),
                                     )
                                     .emit()
                             });
@@ -1024,7 +1070,7 @@ where
 
 #[cfg(test)]
 mod tests {
-  use napi::Either;
+  use crate::BoolOr;
   use swc_core::{
     common::{comments::SingleThreadedComments, Mark},
     ecma::{
@@ -1207,7 +1253,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           ..Default::default()
         },
         None
@@ -1244,7 +1290,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::B(AddComponentElementConfig {
+          add_component_element: BoolOr::Value(AddComponentElementConfig {
             compiler_only: true
           }),
           ..Default::default()
@@ -1283,7 +1329,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           ..Default::default()
         },
         None
@@ -1306,7 +1352,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::B(AddComponentElementConfig {
+          add_component_element: BoolOr::Value(AddComponentElementConfig {
             compiler_only: true
           }),
           ..Default::default()
@@ -1331,7 +1377,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          // add_component_element: Either::A(true),
+          // add_component_element: BoolOr::Bool(true),
           ..Default::default()
         },
         None
@@ -1354,7 +1400,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           simplify_ctor_like_react_lynx_2: true,
           ..Default::default()
         },
@@ -1392,7 +1438,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           simplify_ctor_like_react_lynx_2: true,
           ..Default::default()
         },
@@ -1432,7 +1478,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           simplify_ctor_like_react_lynx_2: true,
           ..Default::default()
         },
@@ -1470,7 +1516,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           simplify_ctor_like_react_lynx_2: true,
           ..Default::default()
         },
@@ -1510,7 +1556,7 @@ mod tests {
       resolver(Mark::new(), Mark::new(), true),
       visit_mut_pass(CompatVisitor::<&SingleThreadedComments>::new(
         CompatVisitorConfig {
-          add_component_element: Either::A(true),
+          add_component_element: BoolOr::Bool(true),
           simplify_ctor_like_react_lynx_2: true,
           ..Default::default()
         },
@@ -1,3 +1,4 @@
+use once_cell::sync::Lazy;
 use regex::Regex;
 use rustc_hash::FxHashSet;
 use swc_core::{
@@ -9,6 +10,8 @@ use swc_core::{
   },
 };
 
+static DEFINE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^__[A-Z_]+__$").unwrap());
+
 pub struct CtorSimplifyVisitor {
   in_constructor: bool,
   is_target_object: bool,
@@ -35,7 +38,6 @@ impl Visit for CtorSimplifyVisitor {
     match &n.body {
       Some(body) => {
         for stmt in &body.stmts {
-          let re = Regex::new(r"^__[A-Z_]+__$").unwrap();
           match stmt {
             Stmt::If(stmt_if) => {
               let mut test_ident = &Ident::dummy();
@@ -51,7 +53,7 @@ impl Visit for CtorSimplifyVisitor {
                 }
               }
 
-              if let Some(_) = re.captures(test_ident.sym.as_str()) {
+              if let Some(_) = DEFINE_RE.captures(test_ident.sym.as_str()) {
                 self.remain_stmts.push(stmt.clone())
               }
             }
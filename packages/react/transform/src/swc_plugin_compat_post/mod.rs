@@ -11,6 +11,7 @@ use swc_core::{
 use crate::{
   swc_plugin_compat::{CompatVisitorConfig, DarkModeConfig},
   swc_plugin_inject::{InjectAs, InjectVisitor, InjectVisitorConfig},
+  BoolOr,
 };
 
 pub struct CompatPostVisitor {
@@ -42,9 +43,9 @@ impl CompatPostVisitor {
           (
             "__DARK_MODE_THEME__".into(),
             InjectAs::Expr(match opts.dark_mode.clone()? {
-              napi::Either::A(false) => None,
-              napi::Either::A(true) => Some("__globalProps.theme".into()),
-              napi::Either::B(DarkModeConfig { theme_expr }) => Some(theme_expr),
+              BoolOr::Bool(false) => None,
+              BoolOr::Bool(true) => Some("__globalProps.theme".into()),
+              BoolOr::Value(DarkModeConfig { theme_expr }) => Some(theme_expr),
             }?),
           ),
         ]),
@@ -85,6 +86,7 @@ mod tests {
   use crate::{
     swc_plugin_compat::{CompatVisitorConfig, DarkModeConfig},
     swc_plugin_compat_post::CompatPostVisitor,
+    BoolOr,
   };
 
   test!(
@@ -101,7 +103,7 @@ mod tests {
         resolver(unresolved_mark, top_level_mark, true),
         visit_mut_pass(CompatPostVisitor::new(
           CompatVisitorConfig {
-            dark_mode: Some(napi::Either::A(true)),
+            dark_mode: Some(BoolOr::Bool(true)),
             ..Default::default()
           },
           unresolved_mark,
@@ -130,7 +132,7 @@ mod tests {
         resolver(unresolved_mark, top_level_mark, true),
         visit_mut_pass(CompatPostVisitor::new(
           CompatVisitorConfig {
-            dark_mode: Some(napi::Either::B(DarkModeConfig {
+            dark_mode: Some(BoolOr::Value(DarkModeConfig {
               theme_expr: "__globalProps.xxx ?? __globalProps.yyy ?? 'zzz'".into(),
             })),
             ..Default::default()
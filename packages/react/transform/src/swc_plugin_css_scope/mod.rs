@@ -1,6 +1,7 @@
 use crate::calc_hash;
-use napi_derive::napi;
+use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
 use swc_core::{
   common::{
     comments::{Comment, CommentKind, Comments},
@@ -12,6 +13,8 @@ use swc_core::{
   },
 };
 
+static CSS_FILE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.(scss|sass|css|less)$").unwrap());
+
 /// CSSScope refers to the
 ///
 /// - `CSSScope::All`: Similar to setting `enableRemoveCSSScope: false`. All CSS files are treated as scoped CSS.
@@ -24,13 +27,15 @@ use swc_core::{
 ///   Instead, we use import types to determinate if the imported CSS is a module.
 ///   - A sideEffects import(`import './foo.module.css`) is not considered as a CSS Module. Even it has `.module.` in filename.
 ///   - A named/namespace/default import is considered as a CSS Module. No matter what the `css-loader` options is given.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CSSScope {
   All,
   None,
   Modules,
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::FromNapiValue for CSSScope {
   unsafe fn from_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -58,6 +63,7 @@ impl napi::bindgen_prelude::FromNapiValue for CSSScope {
   }
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::ToNapiValue for CSSScope {
   unsafe fn to_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -72,7 +78,8 @@ impl napi::bindgen_prelude::ToNapiValue for CSSScope {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct CSSScopeVisitorConfig {
   #[napi(ts_type = "'all' | 'none' | 'modules'")]
   /// @public
@@ -172,8 +179,7 @@ where
       }
       // Is sideEffects import or force scoped
 
-      let re = Regex::new(r"\.(scss|sass|css|less)$").unwrap();
-      if re.is_match(import_decl.src.value.to_string().as_str()) {
+      if CSS_FILE_RE.is_match(import_decl.src.value.to_string().as_str()) {
         // Is CSS files
         //
         // Add cssId to the import
@@ -1,9 +1,10 @@
 use std::{collections::HashMap, fmt::Debug};
 
-use napi_derive::napi;
+use serde::Deserialize;
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct DefineDCEVisitorConfig {
   /// @public
   pub define: HashMap<String, String>,
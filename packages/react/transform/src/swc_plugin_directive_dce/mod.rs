@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use napi_derive::napi;
+use serde::Deserialize;
 use swc_core::{
   common::{errors::HANDLER, Span},
   ecma::{
@@ -9,6 +9,7 @@ use swc_core::{
   },
 };
 
+use crate::diagnostics::{ids, lint};
 use crate::target::TransformTarget;
 
 trait Eliminate {
@@ -36,10 +37,11 @@ impl Eliminate for ArrowExpr {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct DirectiveDCEVisitorConfig {
   /// @internal
-  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED'")]
+  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED' | 'SSR'")]
   pub target: TransformTarget,
 }
 
@@ -67,7 +69,14 @@ impl DirectiveDCEVisitor {
         Stmt::Expr(ExprStmt { expr, span }) => match &**expr {
           Expr::Lit(Lit::Str(str)) => match str.value.to_string().as_str() {
             "use js only" | "background only" | "background-only" => {
-              return (self.opts.target == TransformTarget::LEPUS, Some(*span));
+              // The server renders the first screen as the main thread does.
+              return (
+                matches!(
+                  self.opts.target,
+                  TransformTarget::LEPUS | TransformTarget::SSR
+                ),
+                Some(*span),
+              );
             }
             // directive "main thread" is already handled by `worklet_plugin`, do nothing here
             "use lepus only" => {
@@ -97,7 +106,11 @@ impl VisitMut for DirectiveDCEVisitor {
             if span.is_some() {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(span.unwrap(), "directive inside constructor is not allowed")
+                  .struct_span_warn_with_code(
+                    span.unwrap(),
+                    "directive inside constructor is not allowed",
+                    lint(ids::DIRECTIVE_IN_CONSTRUCTOR),
+                  )
                   .emit();
               });
             }
@@ -135,7 +148,11 @@ impl VisitMut for DirectiveDCEVisitor {
             if span.is_some() {
               HANDLER.with(|handler| {
                 handler
-                  .struct_span_warn(span.unwrap(), "directive inside getter/setter is ignored")
+                  .struct_span_warn_with_code(
+                    span.unwrap(),
+                    "directive inside getter/setter is ignored",
+                    lint(ids::DIRECTIVE_IN_ACCESSOR),
+                  )
                   .emit();
               });
             }
@@ -334,6 +351,30 @@ mod tests {
     "#
   );
 
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |_| visit_mut_pass(DirectiveDCEVisitor::new(DirectiveDCEVisitorConfig {
+      target: TransformTarget::SSR,
+    })),
+    should_eliminate_background_only_in_ssr_target,
+    r#"
+    function keepMe() {
+      function eliminateMe() {
+        'background only';
+        console.log("background only");
+      }
+      function keepMeToo() {
+        'use lepus only';
+        console.log("lepus only");
+      }
+    }
+    "#
+  );
+
   test!(
     module,
     Syntax::Es(EsSyntax {
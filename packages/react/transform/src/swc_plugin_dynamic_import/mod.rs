@@ -1,6 +1,6 @@
 use std::{collections::HashSet, fmt::Debug};
 
-use napi_derive::napi;
+use serde::Deserialize;
 use serde_json::Value;
 use swc_core::{
   common::{
@@ -19,7 +19,8 @@ use swc_core::{
 use crate::utils::jsonify;
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct DynamicImportVisitorConfig {
   /// @internal
   pub runtime_pkg: String,
@@ -36,6 +37,13 @@ impl Default for DynamicImportVisitorConfig {
   }
 }
 
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct DynamicImportManifest {
+  pub specifier: String,
+  pub layer: String,
+}
+
 pub struct DynamicImportVisitor<C>
 where
   C: Comments,
@@ -43,6 +51,7 @@ where
   opts: DynamicImportVisitorConfig,
   named_imports: HashSet<Ident>,
   comments: Option<C>,
+  pub manifest: Vec<DynamicImportManifest>,
 }
 
 impl<C> Default for DynamicImportVisitor<C>
@@ -63,6 +72,7 @@ where
       opts,
       comments,
       named_imports: HashSet::new(),
+      manifest: vec![],
     }
   }
 }
@@ -182,6 +192,11 @@ where
         return;
       }
 
+      self.manifest.push(DynamicImportManifest {
+        specifier: str_lit.to_string(),
+        layer: self.opts.layer.clone(),
+      });
+
       self.comments.add_leading(
         call_expr.args[0].span_lo(),
         Comment {
@@ -191,6 +206,13 @@ where
         },
       );
     } else {
+      if is_import_call_str_lit {
+        self.manifest.push(DynamicImportManifest {
+          specifier: str_lit.to_string(),
+          layer: self.opts.layer.clone(),
+        });
+      }
+
       let ident: Ident = "__dynamicImport".into();
       *call_expr = CallExpr {
         ctxt: call_expr.ctxt,
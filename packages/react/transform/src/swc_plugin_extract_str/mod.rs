@@ -1,4 +1,3 @@
-use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use std::vec;
 use swc_core::{
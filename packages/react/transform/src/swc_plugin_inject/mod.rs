@@ -1,6 +1,6 @@
 use std::{collections::HashMap, fmt::Debug};
 
-use napi_derive::napi;
+use serde::Deserialize;
 use swc_core::{
   common::{errors::HANDLER, sync::Lrc, util::take::Take, FileName, Mark, SourceMap, DUMMY_SP},
   ecma::{
@@ -20,6 +20,30 @@ pub enum InjectAs {
   ImportNamed(String, String),
 }
 
+impl<'de> Deserialize<'de> for InjectAs {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    let v = <Vec<String>>::deserialize(deserializer)?;
+
+    match (v.first().map(String::as_str), v.get(1), v.get(2)) {
+      (Some("expr"), Some(expr), None) => Ok(InjectAs::Expr(expr.clone())),
+      (Some("importDefault"), Some(pkg_name), None) => {
+        Ok(InjectAs::ImportDefault(pkg_name.clone()))
+      }
+      (Some("importStarAs"), Some(pkg_name), None) => Ok(InjectAs::ImportStarAs(pkg_name.clone())),
+      (Some("importNamed"), Some(pkg_name), Some(imported)) => {
+        Ok(InjectAs::ImportNamed(pkg_name.clone(), imported.clone()))
+      }
+      _ => Err(serde::de::Error::custom(
+        "value does not match any variant of enum `InjectAs`",
+      )),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::FromNapiValue for InjectAs {
   unsafe fn from_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -56,6 +80,7 @@ impl napi::bindgen_prelude::FromNapiValue for InjectAs {
   }
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::ToNapiValue for InjectAs {
   unsafe fn to_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -78,7 +103,8 @@ impl napi::bindgen_prelude::ToNapiValue for InjectAs {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct InjectVisitorConfig {
   #[napi(
     ts_type = "Record<string, ['expr', string] | ['importDefault', string] | ['importStarAs', string] | ['importNamed', string, string]>"
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use serde::Deserialize;
 use swc_core::{
   atoms::Atom,
   common::{SyntaxContext, DUMMY_SP},
@@ -17,7 +18,8 @@ use swc_core::{
 use crate::calc_hash;
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct RefreshVisitorConfig {
   pub library: Option<Vec<String>>,
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use napi_derive::napi;
+use serde::Deserialize;
 use swc_core::{
   ecma::ast::*,
   ecma::visit::{VisitMut, VisitMutWith, VisitWith},
@@ -9,7 +9,8 @@ use swc_core::{
 mod is_component_class;
 
 #[napi(object)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct ShakeVisitorConfig {
   /// @public
   pub pkg_name: Vec<String>,
@@ -53,7 +54,10 @@ impl ShakeVisitor {
       import_ids: Vec::new(),
     }
   }
-  pub fn default() -> Self {
+}
+
+impl Default for ShakeVisitor {
+  fn default() -> Self {
     ShakeVisitor::new(ShakeVisitorConfig::default())
   }
 }
@@ -1,3 +1,4 @@
+use once_cell::sync::Lazy;
 use regex::Regex;
 
 use swc_core::ecma::ast::*;
@@ -60,6 +61,26 @@ impl From<Ident> for AttrName {
 }
 
 impl AttrName {
+  /// The name used to describe this attribute in the compile manifest.
+  pub fn to_manifest_name(&self) -> String {
+    match self {
+      AttrName::Attr(name) => name.clone(),
+      AttrName::Dataset(name) => format!("data-{}", name),
+      AttrName::Event(event_type, event_name) => format!("{}:{}", event_type, event_name),
+      AttrName::WorkletEvent(worklet_type, event_type, event_name) => {
+        format!("{}:{}:{}", worklet_type, event_type, event_name)
+      }
+      AttrName::Style | AttrName::ParsedStyle(_) => "style".into(),
+      AttrName::Class => "class".into(),
+      AttrName::ID => "id".into(),
+      AttrName::Ref => "ref".into(),
+      AttrName::TimingFlag => "__lynx_timing_flag".into(),
+      AttrName::WorkletRef(worklet_type) => format!("{}:ref", worklet_type),
+      AttrName::ListItemPlatformInfo => "list-item-platform-info".into(),
+      AttrName::Gesture(ns) => format!("{}:gesture", ns),
+    }
+  }
+
   pub fn from_ns(ns: Ident, name: Ident) -> Self {
     let ns_str = ns.sym.as_ref().to_string();
     let name_str = name.sym.as_ref().to_string();
@@ -75,9 +96,12 @@ impl AttrName {
   }
 }
 
+static EVENT_RE: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"^(global-bind|bind|catch|capture-bind|capture-catch)([A-Za-z]+)$").unwrap()
+});
+
 fn get_event_type_and_name(props_key: &str) -> Option<(String, String)> {
-  let re = Regex::new(r"^(global-bind|bind|catch|capture-bind|capture-catch)([A-Za-z]+)$").unwrap();
-  if let Some(captures) = re.captures(props_key) {
+  if let Some(captures) = EVENT_RE.captures(props_key) {
     let event_type = if captures.get(1).unwrap().as_str().contains("capture") {
       captures.get(1).unwrap().as_str().to_string()
     } else {
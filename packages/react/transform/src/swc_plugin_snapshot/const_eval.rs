@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use swc_core::{
+  common::DUMMY_SP,
+  ecma::ast::{
+    BinExpr, BinaryOp, Bool, Decl, Expr, Id, KeyValueProp, Lit, Module, ModuleDecl, ModuleItem,
+    Null, Number, ObjectLit, Pat, Prop, PropName, PropOrSpread, Stmt, Str, Tpl, UnaryExpr, UnaryOp,
+    VarDeclKind,
+  },
+};
+
+/// A value known at compile time.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstValue {
+  Str(String),
+  Num(f64),
+  Bool(bool),
+  Null,
+}
+
+impl ConstValue {
+  /// The same as `String(value)` in JS, `None` if the number can not be formatted the same way.
+  pub fn to_js_string(&self) -> Option<String> {
+    match self {
+      ConstValue::Str(s) => Some(s.clone()),
+      ConstValue::Num(n) => {
+        // Rust never uses the exponent notation, which JS does for these numbers.
+        if !n.is_finite() || n.abs() >= 1e21 || (*n != 0.0 && n.abs() < 1e-6) {
+          return None;
+        }
+        // `String(-0)` is `"0"`.
+        Some(if *n == 0.0 { "0".into() } else { n.to_string() })
+      }
+      ConstValue::Bool(b) => Some(b.to_string()),
+      ConstValue::Null => Some("null".into()),
+    }
+  }
+
+  pub fn to_lit(&self) -> Lit {
+    match self {
+      ConstValue::Str(s) => Lit::Str(Str {
+        span: DUMMY_SP,
+        value: s.as_str().into(),
+        raw: None,
+      }),
+      ConstValue::Num(n) => Lit::Num(Number {
+        span: DUMMY_SP,
+        value: *n,
+        raw: None,
+      }),
+      ConstValue::Bool(b) => Lit::Bool(Bool {
+        span: DUMMY_SP,
+        value: *b,
+      }),
+      ConstValue::Null => Lit::Null(Null { span: DUMMY_SP }),
+    }
+  }
+}
+
+/// Evaluates the expressions that only depend on literals and the module level `const` bindings.
+///
+/// # Examples
+///
+/// ```js
+/// const BASE = 50;
+/// const PREFIX = 'a';
+///
+/// <view style={{ width: BASE * 2 + 'px' }} className={`a ${PREFIX}`} />;
+/// //                     ^ "100px"                    ^ "a a"
+/// ```
+///
+/// The bindings are identified by [`Id`], so it relies on the `resolver` to tell them from
+/// the shadowing local bindings.
+#[derive(Default)]
+pub struct ConstEvaluator {
+  bindings: HashMap<Id, ConstValue>,
+}
+
+impl ConstEvaluator {
+  /// Collect the `const` declarations at the top level of `module`, in the order of declaration.
+  pub fn from_module(module: &Module) -> Self {
+    let mut evaluator = Self::default();
+    for item in &module.body {
+      let var = match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match &export.decl {
+          Decl::Var(var) => var,
+          _ => continue,
+        },
+        _ => continue,
+      };
+      if var.kind != VarDeclKind::Const || var.declare {
+        continue;
+      }
+      for decl in &var.decls {
+        if let (Pat::Ident(name), Some(init)) = (&decl.name, &decl.init) {
+          if let Some(value) = evaluator.eval(init) {
+            evaluator.bindings.insert(name.to_id(), value);
+          }
+        }
+      }
+    }
+    evaluator
+  }
+
+  pub fn eval(&self, expr: &Expr) -> Option<ConstValue> {
+    match expr {
+      Expr::Lit(Lit::Str(s)) => Some(ConstValue::Str(s.value.to_string())),
+      Expr::Lit(Lit::Num(n)) => Some(ConstValue::Num(n.value)),
+      Expr::Lit(Lit::Bool(b)) => Some(ConstValue::Bool(b.value)),
+      Expr::Lit(Lit::Null(_)) => Some(ConstValue::Null),
+      Expr::Ident(ident) => self.bindings.get(&ident.to_id()).cloned(),
+      Expr::Paren(paren) => self.eval(&paren.expr),
+      Expr::Tpl(tpl) => self.eval_tpl(tpl),
+      Expr::Unary(unary) => self.eval_unary(unary),
+      Expr::Bin(bin) => self.eval_bin(bin),
+      _ => None,
+    }
+  }
+
+  fn eval_tpl(&self, tpl: &Tpl) -> Option<ConstValue> {
+    let mut s = String::new();
+    for (i, quasi) in tpl.quasis.iter().enumerate() {
+      s.push_str(quasi.cooked.as_ref()?);
+      if let Some(expr) = tpl.exprs.get(i) {
+        s.push_str(&self.eval(expr)?.to_js_string()?);
+      }
+    }
+    Some(ConstValue::Str(s))
+  }
+
+  fn eval_unary(&self, unary: &UnaryExpr) -> Option<ConstValue> {
+    match (unary.op, self.eval(&unary.arg)?) {
+      (UnaryOp::Minus, ConstValue::Num(n)) => Some(ConstValue::Num(-n)),
+      (UnaryOp::Plus, ConstValue::Num(n)) => Some(ConstValue::Num(n)),
+      _ => None,
+    }
+  }
+
+  fn eval_bin(&self, bin: &BinExpr) -> Option<ConstValue> {
+    let left = self.eval(&bin.left)?;
+    let right = self.eval(&bin.right)?;
+
+    match (bin.op, &left, &right) {
+      (BinaryOp::Add, ConstValue::Str(_), _) | (BinaryOp::Add, _, ConstValue::Str(_)) => Some(
+        ConstValue::Str(format!("{}{}", left.to_js_string()?, right.to_js_string()?)),
+      ),
+      (op, ConstValue::Num(l), ConstValue::Num(r)) => {
+        let value = match op {
+          BinaryOp::Add => l + r,
+          BinaryOp::Sub => l - r,
+          BinaryOp::Mul => l * r,
+          BinaryOp::Div => l / r,
+          BinaryOp::Mod => l % r,
+          _ => return None,
+        };
+        value.is_finite().then_some(ConstValue::Num(value))
+      }
+      _ => None,
+    }
+  }
+
+  /// Replace `expr` with a literal if it is a constant.
+  ///
+  /// The values of an object literal are folded one by one, so that the constant styles in
+  /// `{ width: BASE * 2 + 'px', height: h }` can still be extracted.
+  pub fn fold(&self, expr: &Expr) -> Expr {
+    if expr.is_lit() {
+      return expr.clone();
+    }
+    if let Some(value) = self.eval(expr) {
+      return Expr::Lit(value.to_lit());
+    }
+
+    match expr {
+      Expr::Object(object) => Expr::Object(ObjectLit {
+        span: object.span,
+        props: object
+          .props
+          .iter()
+          .map(|prop| match prop {
+            PropOrSpread::Prop(p) => match &**p {
+              Prop::KeyValue(kv) => self
+                .fold_prop(kv.key.clone(), &kv.value)
+                .unwrap_or(prop.clone()),
+              Prop::Shorthand(ident) => self
+                .fold_prop(ident.clone().into(), &Expr::Ident(ident.clone()))
+                .unwrap_or(prop.clone()),
+              _ => prop.clone(),
+            },
+            PropOrSpread::Spread(_) => prop.clone(),
+          })
+          .collect(),
+      }),
+      _ => expr.clone(),
+    }
+  }
+
+  fn fold_prop(&self, key: PropName, value: &Expr) -> Option<PropOrSpread> {
+    if value.is_lit() {
+      return None;
+    }
+    let value = self.eval(value)?;
+    Some(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key,
+      value: Box::new(Expr::Lit(value.to_lit())),
+    }))))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use swc_core::{
+    common::{FileName, Globals, Mark, SourceMap, GLOBALS},
+    ecma::{
+      ast::{EsVersion, ModuleItem, Stmt},
+      codegen::to_code,
+      parser::{parse_file_as_module, Syntax},
+      transforms::base::resolver,
+      visit::VisitMutWith,
+    },
+  };
+
+  use super::{ConstEvaluator, ConstValue};
+
+  /// Evaluate the expression of the last statement of `code`.
+  fn eval(code: &str) -> (Option<ConstValue>, String) {
+    GLOBALS.set(&Globals::new(), || {
+      let cm = SourceMap::default();
+      let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+      let mut module = parse_file_as_module(
+        &fm,
+        Syntax::default(),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+      )
+      .unwrap();
+      module.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), false));
+
+      let evaluator = ConstEvaluator::from_module(&module);
+      let Some(ModuleItem::Stmt(Stmt::Expr(stmt))) = module.body.last() else {
+        unreachable!()
+      };
+      let expr = stmt.expr.unwrap_parens();
+      (evaluator.eval(expr), to_code(&evaluator.fold(expr)))
+    })
+  }
+
+  #[test]
+  fn test_eval() {
+    let str = |s: &str| Some(ConstValue::Str(s.into()));
+
+    assert_eq!(eval("const BASE = 50; BASE * 2 + 'px'").0, str("100px"));
+    assert_eq!(eval("const PREFIX = 'a'; `a ${PREFIX}`").0, str("a a"));
+    assert_eq!(
+      eval("const A = 1; export const B = A + 0.5; `${-B}rpx`").0,
+      str("-1.5rpx")
+    );
+    assert_eq!(eval("'a' + 1 + 2").0, str("a12"));
+    assert_eq!(eval("1 + 2 + 'a'").0, str("3a"));
+    assert_eq!(eval("'a' + null + true").0, str("anulltrue"));
+    assert_eq!(eval("(1 + 2) * 3").0, Some(ConstValue::Num(9.0)));
+
+    // Not constants.
+    assert_eq!(eval("let A = 1; A + 'px'").0, None);
+    assert_eq!(eval("A + 'px'").0, None);
+    assert_eq!(eval("1 / 0").0, None);
+    assert_eq!(eval("1e21 + 'px'").0, None);
+    assert_eq!(eval("`${a}`").0, None);
+    // The shadowing binding is not the constant.
+    assert_eq!(
+      eval("const A = 1; function f(A) { return A + 'px' }; A + 'px'").0,
+      str("1px")
+    );
+  }
+
+  #[test]
+  fn test_fold_object() {
+    assert_eq!(
+      eval("const W = 100; ({ width: W + 'px', height: h, W })").1,
+      "{\n    width: \"100px\",\n    height: h,\n    W: 100\n}"
+    );
+  }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use swc_core::{
+  common::{errors::HANDLER, Spanned},
+  ecma::{
+    ast::{Expr, JSXElement, JSXElementChild, JSXExprContainer, Lit},
+    visit::{Visit, VisitWith},
+  },
+};
+
+use super::jsx_helpers::jsx_name;
+use crate::diagnostics::{ids, lint};
+
+/// The children that an element accepts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElementChildren {
+  /// Any children.
+  Any,
+  /// Only texts, e.g.: `<foo>Hello, {name}</foo>`.
+  Text,
+  /// No children.
+  None,
+}
+
+#[cfg(feature = "napi")]
+impl napi::bindgen_prelude::FromNapiValue for ElementChildren {
+  unsafe fn from_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    napi_val: napi::bindgen_prelude::sys::napi_value,
+  ) -> napi::bindgen_prelude::Result<Self> {
+    let val = <&str>::from_napi_value(env, napi_val).map_err(|e| {
+      napi::bindgen_prelude::error!(
+        e.status,
+        "Failed to convert napi value into enum `{}`. {}",
+        "ElementChildren",
+        e,
+      )
+    })?;
+    match val {
+      "any" => Ok(ElementChildren::Any),
+      "text" => Ok(ElementChildren::Text),
+      "none" => Ok(ElementChildren::None),
+      _ => Err(napi::bindgen_prelude::error!(
+        napi::bindgen_prelude::Status::InvalidArg,
+        "value `{}` does not match any variant of enum `{}`",
+        val,
+        "ElementChildren"
+      )),
+    }
+  }
+}
+
+#[cfg(feature = "napi")]
+impl napi::bindgen_prelude::ToNapiValue for ElementChildren {
+  unsafe fn to_napi_value(
+    env: napi::bindgen_prelude::sys::napi_env,
+    val: Self,
+  ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+    let val = match val {
+      ElementChildren::Any => "any",
+      ElementChildren::Text => "text",
+      ElementChildren::None => "none",
+    };
+    <&str>::to_napi_value(env, val)
+  }
+}
+
+/// A native element with a dedicated Element PAPI creator.
+///
+/// ```json
+/// { "map": { "creator": "__CreateMap", "creationAttributes": ["provider"], "children": "none" } }
+/// ```
+///
+/// `<map provider="amap" zoom={zoom} />` is created with `__CreateMap(pageId, { provider: "amap" })`,
+/// and `zoom` is set as usual.
+#[napi(object)]
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ElementConfig {
+  /// @public
+  /// The function to create the element, which is called with `pageId` and the creation attributes.
+  pub creator: String,
+  /// @public
+  /// The attributes that are passed to `creator` instead of being set after the creation.
+  /// They must be static.
+  pub creation_attributes: Option<Vec<String>>,
+  /// @public
+  /// The children that the element accepts, defaults to `any`.
+  #[napi(ts_type = "'any' | 'text' | 'none'")]
+  pub children: Option<ElementChildren>,
+}
+
+/// How `<component>` is created with `__CreateComponent`.
+///
+/// `<component name="header" is="./header" />` is created with
+/// `__CreateComponent(pageId, "", 0, "", "header", "./header", {}, {})`.
+#[napi(object)]
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ComponentConfig {
+  /// @public
+  /// The attribute used as the `name` of the component, defaults to `name`.
+  pub name_attribute: Option<String>,
+  /// @public
+  /// The attribute used as the `path` of the component, defaults to `is`.
+  pub path_attribute: Option<String>,
+}
+
+/// The elements that are created with dedicated creators.
+#[derive(Default)]
+pub struct ElementRegistry {
+  pub elements: HashMap<String, ElementConfig>,
+  pub component: ComponentConfig,
+}
+
+/// Report the children that break the rules of `ElementConfig::children` in a snapshot.
+///
+/// It stops at the expressions, the JSX inside them is validated as another snapshot.
+pub struct ElementChildrenValidator<'a> {
+  pub elements: &'a HashMap<String, ElementConfig>,
+}
+
+impl Visit for ElementChildrenValidator<'_> {
+  fn visit_jsx_element(&mut self, n: &JSXElement) {
+    if let Expr::Lit(Lit::Str(tag)) = *jsx_name(n.opening.name.clone()) {
+      if let Some(element) = self.elements.get(tag.value.as_ref()) {
+        for child in &n.children {
+          let message = match (element.children.unwrap_or(ElementChildren::Any), child) {
+            (_, JSXElementChild::JSXText(text)) if text.value.trim().is_empty() => continue,
+            (ElementChildren::None, _) => format!("`<{}>` can not have children.", tag.value),
+            (ElementChildren::Text, JSXElementChild::JSXElement(_))
+            | (ElementChildren::Text, JSXElementChild::JSXFragment(_)) => {
+              format!("`<{}>` can only have text children.", tag.value)
+            }
+            _ => continue,
+          };
+          HANDLER.with(|handler| {
+            handler
+              .struct_span_err_with_code(child.span(), &message, lint(ids::ELEMENT_CHILDREN))
+              .emit()
+          });
+        }
+      }
+    }
+
+    n.children.visit_with(self);
+  }
+
+  fn visit_jsx_expr_container(&mut self, _: &JSXExprContainer) {}
+}
@@ -10,6 +10,8 @@ use swc_core::{
   },
 };
 
+use super::attr_name::AttrName;
+
 pub fn jsx_name(name: JSXElementName) -> Box<Expr> {
   let span = name.span();
   match name {
@@ -110,12 +112,12 @@ pub fn jsx_attr_to_prop(attr: &JSXAttr) -> PropOrSpread {
   prop
 }
 
-pub fn jsx_props_to_obj(jsx: &JSXElement) -> Option<ObjectLit> {
+pub fn jsx_props_to_obj(attrs: &[JSXAttrOrSpread]) -> Option<ObjectLit> {
   let mut obj = ObjectLit {
     span: DUMMY_SP,
     props: vec![],
   };
-  for attr in &jsx.opening.attrs {
+  for attr in attrs {
     match attr {
       JSXAttrOrSpread::JSXAttr(attr) => {
         obj.props.push(jsx_attr_to_prop(attr));
@@ -298,6 +300,45 @@ pub fn jsx_is_children_full_dynamic(n: &JSXElement) -> bool {
       })
 }
 
+/// Whether `jsx` can be created entirely by the snapshot creator, i.e.: it has no custom
+/// elements, lists, keys or expressions, and all of its attributes are literals without
+/// events or refs.
+pub fn jsx_is_static(jsx: &JSXElement) -> bool {
+  if jsx_is_custom(jsx) || jsx_is_list(jsx) {
+    return false;
+  }
+
+  let attrs_are_static = jsx.opening.attrs.iter().all(|attr| match attr {
+    JSXAttrOrSpread::JSXAttr(JSXAttr {
+      name: JSXAttrName::Ident(name),
+      value: None | Some(JSXAttrValue::Lit(Lit::Str(_))),
+      ..
+    }) => {
+      name.sym != atom!("key")
+        && matches!(
+          AttrName::from(name.sym.to_string()),
+          AttrName::Attr(_)
+            | AttrName::Dataset(_)
+            | AttrName::Class
+            | AttrName::ID
+            | AttrName::Style
+        )
+    }
+    _ => false,
+  });
+
+  attrs_are_static
+    && jsx.children.iter().all(|child| match child {
+      JSXElementChild::JSXText(_) => true,
+      JSXElementChild::JSXElement(element) => jsx_is_static(element),
+      JSXElementChild::JSXExprContainer(JSXExprContainer {
+        expr: JSXExpr::JSXEmptyExpr(_),
+        ..
+      }) => true,
+      _ => false,
+    })
+}
+
 // Copied from https://github.com/swc-project/swc/blob/main/crates/swc_ecma_transforms_react/src/jsx/mod.rs#L1423
 pub fn transform_jsx_attr_str(v: &str) -> String {
   let single_quote = false;
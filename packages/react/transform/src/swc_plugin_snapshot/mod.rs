@@ -1,44 +1,62 @@
 use std::{
   cell::RefCell,
   collections::{HashMap, HashSet},
+  iter,
+  rc::Rc,
 };
 
 use once_cell::sync::Lazy;
+use serde::Deserialize;
 use swc_core::{
   common::{
-    comments::{CommentKind, Comments},
-    errors::HANDLER,
+    comments::Comments,
+    errors::{SourceMapperDyn, HANDLER},
     sync::Lrc,
     util::take::Take,
-    Mark, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP,
+    Mark, Span, Spanned, SyntaxContext, DUMMY_SP,
   },
   ecma::{
     ast::{JSXExpr, *},
+    codegen::to_code,
     utils::{is_literal, prepend_stmt, private_ident},
-    visit::{VisitMut, VisitMutWith},
+    visit::{VisitMut, VisitMutWith, VisitWith},
   },
   quote, quote_expr,
 };
 
 mod attr_name;
+mod const_eval;
+mod element;
 mod jsx_helpers;
 mod slot_marker;
+mod ssr;
 
 use crate::{
   css::{get_inline_style_from_object, get_string_inline_style_from_literal},
+  diagnostics::{ids, lint},
+  panic_guard::record_span,
+  pragma::Pragmas,
   target::TransformTarget,
-  utils::calc_hash,
+  utils::{calc_hash, calc_hash_with_len, CanonicalIdents},
   TransformMode,
 };
 
+pub use self::element::{ComponentConfig, ElementChildren, ElementConfig};
+use self::element::{ElementChildrenValidator, ElementRegistry};
+
 use self::{
   attr_name::AttrName,
+  const_eval::ConstEvaluator,
   jsx_helpers::{
     jsx_attr_name, jsx_attr_to_prop, jsx_attr_value, jsx_children_to_expr,
     jsx_is_children_full_dynamic, jsx_is_custom, jsx_is_list, jsx_is_list_item, jsx_name,
     jsx_props_to_obj, jsx_text_to_str, transform_jsx_attr_str,
   },
-  slot_marker::{jsx_is_internal_slot, jsx_unwrap_internal_slot, WrapperMarker},
+  slot_marker::{
+    is_boolean, jsx_is_internal_slot, jsx_is_internal_switch, jsx_unwrap_internal_slot,
+    paren_if_needed, SwitchMarker, WrapperMarker,
+  },
+  ssr::SnapshotRenderer,
 };
 
 // impl From<i32> for Expr {
@@ -102,13 +120,54 @@ static NO_FLATTEN_ATTRIBUTES: Lazy<HashSet<String>> = Lazy::new(|| {
   ])
 });
 
+static LIST_ITEM_PLATFORM_INFO_ATTRIBUTES: Lazy<HashSet<String>> = Lazy::new(|| {
+  HashSet::from([
+    "reuse-identifier".to_string(),
+    "full-span".to_string(),
+    "item-key".to_string(),
+    "sticky-top".to_string(),
+    "sticky-bottom".to_string(),
+    "estimated-height".to_string(),
+    "estimated-height-px".to_string(),
+    "estimated-main-axis-size-px".to_string(),
+  ])
+});
+
+/// The key of `attr` in the spread object after `transformSpread` of the runtime.
+fn spread_key(attr: &JSXAttr) -> String {
+  let name = jsx_attr_name(&attr.name).to_string();
+  match AttrName::from(name.clone()) {
+    AttrName::Class => "className".into(),
+    _ => name,
+  }
+}
+
+/// Whether `attr` after a spread should still be handled by `updateSpread`, which sets the
+/// dataset and the platform info of list items as a whole.
+fn is_kept_in_spread(attr: &JSXAttr, is_list_item: bool) -> bool {
+  let name = jsx_attr_name(&attr.name).to_string();
+  (is_list_item && LIST_ITEM_PLATFORM_INFO_ATTRIBUTES.contains(&name))
+    || matches!(AttrName::from(name), AttrName::Dataset(_))
+}
+
 #[derive(Debug)]
 pub enum DynamicPart {
   Attr(Expr, i32, AttrName),
-  Spread(Expr, i32),
+  Spread(
+    Expr,
+    i32,
+    /* the keys owned by the attributes after the spread */ Vec<String>,
+  ),
   Slot(JSXElement, i32),
   Children(Expr, i32),
   ListChildren(Expr, i32),
+  /// Shows the first branch under the wrapper element when the value is truthy, and the second
+  /// one (if any) otherwise. The branches are created by the snapshot and toggled by the updater.
+  Switch(
+    Expr,
+    i32,
+    /* the element indices of the branches */ Vec<i32>,
+  ),
 }
 
 pub fn i32_to_expr(i: &i32) -> Expr {
@@ -120,6 +179,24 @@ pub fn i32_to_expr(i: &i32) -> Expr {
 }
 
 impl DynamicPart {
+  fn to_manifest(&self) -> DynamicPartManifest {
+    let (kind, element_index, attr) = match self {
+      DynamicPart::Attr(_, element_index, attr_name) => {
+        ("attr", element_index, Some(attr_name.to_manifest_name()))
+      }
+      DynamicPart::Spread(_, element_index, _) => ("spread", element_index, None),
+      DynamicPart::Slot(_, element_index) => ("slot", element_index, None),
+      DynamicPart::Children(_, element_index) => ("children", element_index, None),
+      DynamicPart::ListChildren(_, element_index) => ("listChildren", element_index, None),
+      DynamicPart::Switch(_, element_index, _) => ("switch", element_index, None),
+    };
+    DynamicPartManifest {
+      kind: kind.into(),
+      element_index: *element_index,
+      attr,
+    }
+  }
+
   fn to_updater(&self, runtime_id: Expr, target: TransformTarget, exp_index: i32) -> Expr {
     match target {
       TransformTarget::LEPUS | TransformTarget::MIXED => match self {
@@ -248,16 +325,60 @@ impl DynamicPart {
             ns: Expr = Expr::Lit(Lit::Str(ns.clone().into())),
           ),
         },
-        DynamicPart::Spread(_, element_index) => quote!(
+        DynamicPart::Spread(_, element_index, excluded_keys) if excluded_keys.is_empty() => quote!(
           "(snapshot, index, oldValue) => $runtime_id.updateSpread(snapshot, index, oldValue, $element_index)" as Expr,
           runtime_id: Expr = runtime_id.clone(),
           element_index: Expr = i32_to_expr(element_index)
         ),
+        DynamicPart::Spread(_, element_index, excluded_keys) => quote!(
+          "(snapshot, index, oldValue) => $runtime_id.updateSpread(snapshot, index, oldValue, $element_index, $excluded_keys)" as Expr,
+          runtime_id: Expr = runtime_id.clone(),
+          element_index: Expr = i32_to_expr(element_index),
+          excluded_keys: Expr = Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: excluded_keys
+              .iter()
+              .map(|key| Some(Expr::Lit(Lit::Str(key.as_str().into())).into()))
+              .collect(),
+          }),
+        ),
         DynamicPart::Slot(_, _) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
         DynamicPart::Children(_, _) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
         DynamicPart::ListChildren(_, _) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        DynamicPart::Switch(_, element_index, branches) => match branches.as_slice() {
+          [branch] => quote!(
+            "function (ctx, index, oldValue) {
+              if (ctx.__elements) {
+                if (oldValue) {
+                  __RemoveElement(ctx.__elements[$element_index], ctx.__elements[$branch]);
+                }
+                if (ctx.__values[$exp_index]) {
+                  __AppendElement(ctx.__elements[$element_index], ctx.__elements[$branch]);
+                }
+              }
+            }" as Expr,
+            element_index: Expr = i32_to_expr(element_index),
+            branch: Expr = i32_to_expr(branch),
+            exp_index: Expr = i32_to_expr(&exp_index),
+          ),
+          [consequent, alternate] => quote!(
+            "function (ctx, index, oldValue) {
+              if (ctx.__elements) {
+                if (oldValue !== undefined) {
+                  __RemoveElement(ctx.__elements[$element_index], ctx.__elements[oldValue ? $consequent : $alternate]);
+                }
+                __AppendElement(ctx.__elements[$element_index], ctx.__elements[ctx.__values[$exp_index] ? $consequent : $alternate]);
+              }
+            }" as Expr,
+            element_index: Expr = i32_to_expr(element_index),
+            consequent: Expr = i32_to_expr(consequent),
+            alternate: Expr = i32_to_expr(alternate),
+            exp_index: Expr = i32_to_expr(&exp_index),
+          ),
+          _ => unreachable!("A switch has one or two branches."),
+        },
       },
-      TransformTarget::JS => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+      TransformTarget::JS | TransformTarget::SSR => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
     }
   }
 }
@@ -269,8 +390,12 @@ where
   page_id: Lazy<Ident>,
   runtime_id: Expr,
   parent_element: Option<Ident>,
+  parent_element_index: Option<i32>,
   element_index: i32,
+  element_manifests: Vec<(Option<i32>, SnapshotElementManifest)>,
   element_ids: HashMap<i32, Ident>,
+  /// The elements with the spans of the JSX that creates them.
+  element_spans: Vec<(Ident, Span)>,
   static_stmts: Vec<RefCell<Stmt>>,
   si_id: Lazy<Ident>,
   snapshot_creator: Option<Function>,
@@ -278,19 +403,32 @@ where
   dynamic_parts: Vec<DynamicPart>,
   dynamic_part_visitor: &'a mut V,
   key: Option<JSXAttrValue>,
+  const_evaluator: Rc<ConstEvaluator>,
+  registry: Rc<ElementRegistry>,
+  /// The next element is a branch of a switch, which is not appended to its parent on creation.
+  is_switch_branch: bool,
 }
 
 impl<'a, V> DynamicPartExtractor<'a, V>
 where
   V: VisitMut,
 {
-  fn new(runtime_id: Expr, dynamic_part_count: i32, dynamic_part_visitor: &'a mut V) -> Self {
+  fn new(
+    runtime_id: Expr,
+    dynamic_part_count: i32,
+    const_evaluator: Rc<ConstEvaluator>,
+    registry: Rc<ElementRegistry>,
+    dynamic_part_visitor: &'a mut V,
+  ) -> Self {
     DynamicPartExtractor {
       page_id: Lazy::new(|| private_ident!("pageId")),
       runtime_id,
       parent_element: None,
+      parent_element_index: None,
       element_index: 0,
+      element_manifests: vec![],
       element_ids: HashMap::new(),
+      element_spans: vec![],
       static_stmts: vec![],
       si_id: Lazy::new(|| private_ident!("snapshotInstance")),
       snapshot_creator: None,
@@ -298,12 +436,260 @@ where
       dynamic_parts: vec![],
       dynamic_part_visitor,
       key: None,
+      const_evaluator,
+      registry,
+      is_switch_branch: false,
+    }
+  }
+
+  fn push_element_manifest(&mut self, tag: String, text: Option<String>) {
+    self.element_manifests.push((
+      self.parent_element_index,
+      SnapshotElementManifest {
+        index: self.element_index,
+        tag,
+        text,
+        children: vec![],
+      },
+    ));
+  }
+
+  /// Create the branches of `<internal-switch>` under a wrapper element, see `DynamicPart::Switch`.
+  fn visit_mut_switch(&mut self, n: &mut JSXElement) {
+    let parent_el = self
+      .parent_element
+      .clone()
+      .expect("A switch is always a child of an element.");
+    let el = private_ident!("el");
+    self.element_ids.insert(self.element_index, el.clone());
+    self.push_element_manifest("wrapper".into(), None);
+    self.static_stmts.push(RefCell::new(quote!(
+      r#"const $element = __CreateWrapperElement($page_id)"# as Stmt,
+      element = el.clone(),
+      page_id = self.page_id.clone(),
+    )));
+    self.static_stmts.push(RefCell::new(quote!(
+      r#"__AppendElement($parent, $child)"# as Stmt,
+      parent: Ident = parent_el,
+      child: Ident = el.clone(),
+    )));
+
+    let switch_element_index = self.element_index;
+    self.element_index += 1;
+
+    let pre_parent_element = self.parent_element.replace(el);
+    let pre_parent_element_index = self.parent_element_index.replace(switch_element_index);
+    let mut branches = vec![];
+    for child in n.children.iter_mut() {
+      if let JSXElementChild::JSXElement(branch) = child {
+        branches.push(self.element_index);
+        self.is_switch_branch = true;
+        branch.visit_mut_with(self);
+      }
+    }
+    self.parent_element = pre_parent_element;
+    self.parent_element_index = pre_parent_element_index;
+
+    let condition = match n.opening.attrs.pop() {
+      Some(JSXAttrOrSpread::JSXAttr(JSXAttr { value, .. })) => *jsx_attr_value(value),
+      _ => unreachable!("The condition of a switch is its only attribute."),
+    };
+    // The values are compared by the runtime, so they are normalized to booleans.
+    let condition = if is_boolean(&condition) {
+      condition
+    } else {
+      Expr::Unary(UnaryExpr {
+        span: DUMMY_SP,
+        op: UnaryOp::Bang,
+        arg: Box::new(Expr::Unary(UnaryExpr {
+          span: DUMMY_SP,
+          op: UnaryOp::Bang,
+          arg: Box::new(paren_if_needed(condition)),
+        })),
+      })
+    };
+    self.dynamic_parts.push(DynamicPart::Switch(
+      condition,
+      switch_element_index,
+      branches,
+    ));
+  }
+
+  /// Build the element tree of the snapshot from the elements collected in pre-order.
+  fn element_manifest_tree(&self, parent: Option<i32>) -> Vec<SnapshotElementManifest> {
+    self
+      .element_manifests
+      .iter()
+      .filter(|(p, _)| *p == parent)
+      .map(|(_, element)| SnapshotElementManifest {
+        children: self.element_manifest_tree(Some(element.index)),
+        ..element.clone()
+      })
+      .collect()
+  }
+
+  /// Take the static `names` attributes out of `n`, which are passed to `creator` at creation.
+  ///
+  /// The attributes are kept if there is a spread which may override them, or if they are not
+  /// static.
+  fn take_creation_attributes(
+    &self,
+    n: &mut JSXElement,
+    tag: &str,
+    creator: &str,
+    names: &[String],
+  ) -> Vec<(String, Lit)> {
+    let mut creation_attrs = vec![];
+    if n
+      .opening
+      .attrs
+      .iter()
+      .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+    {
+      return creation_attrs;
+    }
+
+    n.opening.attrs.retain(|attr| {
+      let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+        return true;
+      };
+      let name = jsx_attr_name(&attr.name).to_string();
+      if !names.contains(&name) {
+        return true;
+      }
+
+      let value = match &attr.value {
+        None => Some(Lit::Bool(true.into())),
+        Some(JSXAttrValue::Lit(lit)) => Some(lit.clone()),
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        })) => match self.const_evaluator.fold(expr) {
+          Expr::Lit(lit) => Some(lit),
+          _ => None,
+        },
+        _ => None,
+      };
+      match value {
+        Some(value) => {
+          creation_attrs.push((name, value));
+          false
+        }
+        None => {
+          HANDLER.with(|handler| {
+            handler
+              .struct_span_warn_with_code(
+                attr.span,
+                &format!(
+                  "`{}` of `<{}>` is passed to `{}` at creation, it should be static. Fallback to setting it after the creation.",
+                  name, tag, creator
+                ),
+                lint(ids::ELEMENT_CREATION_ATTRIBUTE),
+              )
+              .emit()
+          });
+          true
+        }
+      }
+    });
+
+    creation_attrs
+  }
+
+  /// Create a custom element with its creator, e.g.: `__CreateMap(pageId, { provider: "amap" })`.
+  fn static_stmt_from_element_config(
+    &mut self,
+    n: &mut JSXElement,
+    el: Ident,
+    tag: &str,
+    element: &ElementConfig,
+  ) -> Stmt {
+    let creation_props = self
+      .take_creation_attributes(
+        n,
+        tag,
+        &element.creator,
+        element.creation_attributes.as_deref().unwrap_or_default(),
+      )
+      .into_iter()
+      .map(|(name, value)| {
+        Prop::KeyValue(KeyValueProp {
+          key: PropName::Str(name.into()),
+          value: Box::new(Expr::Lit(value)),
+        })
+        .into()
+      })
+      .collect::<Vec<PropOrSpread>>();
+
+    let creator = Expr::Ident(Ident::new(
+      element.creator.as_str().into(),
+      DUMMY_SP,
+      SyntaxContext::default(),
+    ));
+    if creation_props.is_empty() {
+      quote!(
+        r#"const $element = $creator($page_id)"# as Stmt,
+        element = el,
+        creator: Expr = creator,
+        page_id = self.page_id.clone(),
+      )
+    } else {
+      quote!(
+        r#"const $element = $creator($page_id, $props)"# as Stmt,
+        element = el,
+        creator: Expr = creator,
+        page_id = self.page_id.clone(),
+        props: Expr = Expr::Object(ObjectLit {
+          span: DUMMY_SP,
+          props: creation_props,
+        }),
+      )
     }
   }
 
-  fn static_stmt_from_jsx_element(&mut self, n: &JSXElement, el: Ident) -> Stmt {
+  /// Create `<component>` with `__CreateComponent`, see `ComponentConfig`.
+  fn static_stmt_from_component(&mut self, n: &mut JSXElement, el: Ident) -> Stmt {
+    let component = &self.registry.component;
+    let name_attribute = component.name_attribute.as_deref().unwrap_or("name");
+    let path_attribute = component.path_attribute.as_deref().unwrap_or("is");
+
+    let creation_attrs = self.take_creation_attributes(
+      n,
+      "component",
+      "__CreateComponent",
+      &[name_attribute.to_string(), path_attribute.to_string()],
+    );
+    let creation_attr = |name: &str| {
+      creation_attrs
+        .iter()
+        .find(|(attr, _)| attr == name)
+        .map_or(Expr::Lit(Lit::Str("".into())), |(_, value)| {
+          Expr::Lit(value.clone())
+        })
+    };
+
+    quote!(
+      r#"const $element = __CreateComponent($page_id, "", 0, "", $name, $path, {}, {})"# as Stmt,
+      element = el,
+      page_id = self.page_id.clone(),
+      name: Expr = creation_attr(name_attribute),
+      path: Expr = creation_attr(path_attribute),
+    )
+  }
+
+  fn static_stmt_from_jsx_element(&mut self, n: &mut JSXElement, el: Ident) -> Stmt {
     let mut static_stmt: Stmt = Stmt::Empty(EmptyStmt { span: DUMMY_SP });
 
+    if let Expr::Lit(Lit::Str(tag)) = *jsx_name(n.opening.name.clone()) {
+      let registry = self.registry.clone();
+      if let Some(element) = registry.elements.get(tag.value.as_ref()) {
+        return self.static_stmt_from_element_config(n, el, &tag.value, element);
+      }
+      if tag.value.as_ref() == "component" {
+        return self.static_stmt_from_component(n, el);
+      }
+    }
+
     match *jsx_name(n.opening.name.clone()) {
       Expr::Lit(lit) => {
         match lit {
@@ -386,6 +772,7 @@ where
   V: VisitMut,
 {
   fn visit_mut_jsx_element(&mut self, n: &mut JSXElement) {
+    record_span(n.span);
     if jsx_is_internal_slot(&n) {
       if self.dynamic_part_count > 1 {
         n.visit_mut_children_with(self.dynamic_part_visitor);
@@ -399,7 +786,13 @@ where
       }
     }
 
+    if jsx_is_internal_switch(n) {
+      self.visit_mut_switch(n);
+      return;
+    }
+
     if !jsx_is_custom(&n) {
+      let is_switch_branch = std::mem::take(&mut self.is_switch_branch);
       match Lazy::<Ident>::get(&self.page_id) {
         Some(_) => {}
         None => {
@@ -413,6 +806,13 @@ where
 
       let el = private_ident!("el");
       self.element_ids.insert(self.element_index, el.clone());
+      if !n.span.is_dummy() {
+        self.element_spans.push((el.clone(), n.span));
+      }
+
+      if let Expr::Lit(Lit::Str(tag)) = *jsx_name(n.opening.name.clone()) {
+        self.push_element_manifest(tag.value.to_string(), None);
+      }
 
       let static_stmt = self.static_stmt_from_jsx_element(n, el.clone());
       let static_stmt = RefCell::new(static_stmt);
@@ -468,19 +868,9 @@ where
             match attr_or_spread {
               JSXAttrOrSpread::JSXAttr(attr) => {
                 if let JSXAttrName::Ident(id) = &attr.name {
-                  match id.sym.to_string().as_str() {
-                    "reuse-identifier"
-                    | "full-span"
-                    | "item-key"
-                    | "sticky-top"
-                    | "sticky-bottom"
-                    | "estimated-height"
-                    | "estimated-height-px"
-                    | "estimated-main-axis-size-px" => {
-                      list_item_platform_info.push(attr.clone());
-                      return false;
-                    }
-                    &_ => {}
+                  if LIST_ITEM_PLATFORM_INFO_ATTRIBUTES.contains(id.sym.as_ref()) {
+                    list_item_platform_info.push(attr.clone());
+                    return false;
                   }
                 }
               }
@@ -525,7 +915,11 @@ where
                   // warn about key not on root element
                   HANDLER.with(|handler| {
                     handler
-                      .struct_span_warn(*span, "key is not on root element of snapshot")
+                      .struct_span_warn_with_code(
+                        *span,
+                        "key is not on root element of snapshot",
+                        lint(ids::KEY_NOT_ON_ROOT),
+                      )
                       .emit()
                   });
                 }
@@ -537,9 +931,31 @@ where
           },
         });
 
-      if has_spread_element {
-        // TODO: avoid clone
-        let mut spread_obj = jsx_props_to_obj(n).unwrap();
+      if let Some(last_spread) = n
+        .opening
+        .attrs
+        .iter()
+        .rposition(|attr_or_spread| matches!(attr_or_spread, JSXAttrOrSpread::SpreadElement(_)))
+      {
+        // The attributes after the last spread always override it, so they are compiled as usual
+        // and `updateSpread` skips their keys. The others are kept in the spread object.
+        let is_list_item = jsx_is_list_item(n);
+        let mut excluded_keys: Vec<String> = vec![];
+        let mut compiled_attrs = vec![];
+        for attr_or_spread in n.opening.attrs.split_off(last_spread + 1) {
+          match &attr_or_spread {
+            JSXAttrOrSpread::JSXAttr(attr) if !is_kept_in_spread(attr, is_list_item) => {
+              let key = spread_key(attr);
+              if !excluded_keys.contains(&key) {
+                excluded_keys.push(key);
+              }
+              compiled_attrs.push(attr_or_spread);
+            }
+            _ => n.opening.attrs.push(attr_or_spread),
+          }
+        }
+
+        let mut spread_obj = jsx_props_to_obj(&n.opening.attrs).unwrap();
         spread_obj.props.push(
           Prop::KeyValue(KeyValueProp {
             key: PropName::Ident(IdentName::new("__spread".into(), DUMMY_SP)),
@@ -550,15 +966,21 @@ where
         self.dynamic_parts.push(DynamicPart::Spread(
           Expr::Object(spread_obj),
           self.element_index,
+          excluded_keys,
         ));
-      } else {
+        n.opening.attrs = compiled_attrs;
+      }
+
+      {
         let el = Expr::Ident(el.clone());
 
         n.opening
           .attrs
           .iter_mut()
           .for_each(|attr_or_spread| match attr_or_spread {
-            JSXAttrOrSpread::SpreadElement(_) => todo!(),
+            JSXAttrOrSpread::SpreadElement(_) => {
+              unreachable!("The spread attributes should be moved into `DynamicPart::Spread`.")
+            }
             JSXAttrOrSpread::JSXAttr(JSXAttr { name, value, .. }) => {
               if let Some(JSXAttrValue::Lit(lit)) = value {
                 match lit {
@@ -605,7 +1027,7 @@ where
                           // expr.map_with_mut(|value| {
                           //     value.fold_with(self.dynamic_part_visitor)
                           // });
-                          match &**expr {
+                          match &self.const_evaluator.fold(expr) {
                             Expr::Lit(value) => {
                               let stmt = quote!(
                                   r#"__SetAttribute($element, $name, $value)"# as Stmt,
@@ -655,13 +1077,24 @@ where
                         Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
                           expr: JSXExpr::Expr(expr),
                           ..
-                        })) => {
-                          self.dynamic_parts.push(DynamicPart::Attr(
-                            *expr.clone(),
-                            self.element_index,
-                            attr_name.clone(),
-                          ));
-                        }
+                        })) => match &self.const_evaluator.fold(expr) {
+                          Expr::Lit(value) => {
+                            let stmt = quote!(
+                                r#"__AddDataset($element, $name, $value)"# as Stmt,
+                                element: Expr = el.clone(),
+                                name: Expr =  name.clone().into(),
+                                value: Expr = Expr::Lit(value.clone())
+                            );
+                            self.static_stmts.push(RefCell::new(stmt));
+                          }
+                          _ => {
+                            self.dynamic_parts.push(DynamicPart::Attr(
+                              *expr.clone(),
+                              self.element_index,
+                              attr_name.clone(),
+                            ));
+                          }
+                        },
                         Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
@@ -701,7 +1134,8 @@ where
                           span,
                           ..
                         })) => {
-                          let expr = &**expr;
+                          // <view style={{width: BASE * 2 + 'px'}} />;
+                          let expr = &self.const_evaluator.fold(expr);
                           if is_literal(expr) {
                             let s = get_string_inline_style_from_literal(expr, span);
 
@@ -749,7 +1183,6 @@ where
                                   return Some(block);
                                 }
 
-                                // TODO: use eval here
                                 if is_literal(&value) {
                                   let stmt = quote!(
                                     r#"__AddInlineStyle($element, $key, $value)"# as Stmt,
@@ -810,7 +1243,7 @@ where
                         Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
                           expr: JSXExpr::Expr(expr),
                           ..
-                        })) => match &**expr {
+                        })) => match &self.const_evaluator.fold(expr) {
                           Expr::Lit(value) => {
                             let stmt = quote!(
                                 r#"__SetClasses($element, $value)"# as Stmt,
@@ -849,13 +1282,23 @@ where
                         Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
                           expr: JSXExpr::Expr(expr),
                           ..
-                        })) => {
-                          self.dynamic_parts.push(DynamicPart::Attr(
-                            *expr.clone(),
-                            self.element_index,
-                            attr_name,
-                          ));
-                        }
+                        })) => match &self.const_evaluator.fold(expr) {
+                          Expr::Lit(value) => {
+                            let stmt = quote!(
+                                r#"__SetID($element, $value)"# as Stmt,
+                                element: Expr = el.clone(),
+                                value: Expr = Expr::Lit(value.clone())
+                            );
+                            self.static_stmts.push(RefCell::new(stmt));
+                          }
+                          _ => {
+                            self.dynamic_parts.push(DynamicPart::Attr(
+                              *expr.clone(),
+                              self.element_index,
+                              attr_name,
+                            ));
+                          }
+                        },
                         Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
                           expr: JSXExpr::JSXEmptyExpr(_),
                           ..
@@ -899,6 +1342,7 @@ where
       }
 
       match &self.parent_element {
+        Some(_) if is_switch_branch => {}
         Some(parent_el) => {
           self.static_stmts.push(RefCell::new(quote!(
               r#"__AppendElement($parent, $child)"# as Stmt,
@@ -916,7 +1360,9 @@ where
         self.element_index += 1;
 
         let pre_parent_element = self.parent_element.take();
+        let pre_parent_element_index = self.parent_element_index.take();
         self.parent_element = Some(el.clone());
+        self.parent_element_index = Some(self.element_index - 1);
         // n.children.iter_mut().for_each(|child| match child {
         //     JSXElementChild::JSXText(_) => {
         //         child.visit_mut_children_with(self);
@@ -947,6 +1393,7 @@ where
         n.visit_mut_children_with(self);
 
         self.parent_element = pre_parent_element;
+        self.parent_element_index = pre_parent_element_index;
       } else {
         if self.dynamic_part_count <= 1 {
           n.visit_mut_children_with(self.dynamic_part_visitor);
@@ -1060,6 +1507,7 @@ where
     if !t.is_empty() {
       let el = private_ident!("el");
       self.element_ids.insert(self.element_index, el.clone());
+      self.push_element_manifest("raw-text".into(), Some(t.to_string()));
 
       self.static_stmts.push(RefCell::new(quote!(
           r#"const $element = __CreateRawText($t)"# as Stmt,
@@ -1083,18 +1531,147 @@ where
   }
 }
 
+/// The structural fingerprint of a snapshot definition, i.e.: the element types, the static
+/// attributes and the layout of the dynamic parts.
+fn snapshot_fingerprint(
+  creator: &Function,
+  dynamic_parts_def: Vec<Option<ExprOrSpread>>,
+  slot: &Expr,
+  css_id: &Expr,
+) -> String {
+  let mut def = Expr::Array(ArrayLit {
+    span: DUMMY_SP,
+    elems: [
+      Expr::Fn(FnExpr {
+        ident: None,
+        function: Box::new(creator.clone()),
+      }),
+      Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: dynamic_parts_def,
+      }),
+      slot.clone(),
+      css_id.clone(),
+    ]
+    .into_iter()
+    .map(|expr| {
+      Some(ExprOrSpread {
+        spread: None,
+        expr: Box::new(expr),
+      })
+    })
+    .collect(),
+  });
+  def.visit_mut_with(&mut CanonicalIdents::default());
+  calc_hash_with_len(&to_code(&def), 16)
+}
+
+/// `<snapshot_id values={[...]}>{...children}</snapshot_id>`
+fn snapshot_jsx_element(
+  span: Span,
+  snapshot_id: Ident,
+  mut attrs: Vec<JSXAttrOrSpread>,
+  values: Option<Vec<Option<ExprOrSpread>>>,
+  children: Vec<JSXElementChild>,
+  self_closing: bool,
+) -> JSXElement {
+  if let Some(values) = values {
+    attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+      span: DUMMY_SP,
+      name: JSXAttrName::Ident(IdentName::new("values".into(), DUMMY_SP)),
+      value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+        span: DUMMY_SP,
+        expr: JSXExpr::Expr(Box::new(Expr::Array(ArrayLit {
+          span: DUMMY_SP,
+          elems: values,
+        }))),
+      })),
+    }));
+  }
+
+  JSXElement {
+    span,
+    opening: JSXOpeningElement {
+      name: JSXElementName::Ident(snapshot_id.clone()),
+      span,
+      attrs,
+      self_closing,
+      type_args: None,
+    },
+    children,
+    closing: if self_closing {
+      None
+    } else {
+      Some(JSXClosingElement {
+        name: JSXElementName::Ident(snapshot_id),
+        span: DUMMY_SP,
+      })
+    },
+  }
+}
+
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct SnapshotElementManifest {
+  /// The index of the element in `ctx.__elements`.
+  pub index: i32,
+  pub tag: String,
+  /// The content of the `raw-text` element.
+  pub text: Option<String>,
+  pub children: Vec<SnapshotElementManifest>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct DynamicPartManifest {
+  #[napi(ts_type = "'attr' | 'spread' | 'slot' | 'children' | 'listChildren' | 'switch'")]
+  pub kind: String,
+  pub element_index: i32,
+  /// The attribute name of an `attr` dynamic part.
+  pub attr: Option<String>,
+}
+
 #[napi(object)]
 #[derive(Clone, Debug)]
+pub struct SnapshotManifest {
+  pub uid: String,
+  /// The structural fingerprint, the snapshots with the same fingerprint share one definition.
+  pub fingerprint: String,
+  pub elements: Vec<SnapshotElementManifest>,
+  pub dynamic_parts: Vec<DynamicPartManifest>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct JSXTransformerConfig {
   pub preserve_jsx: bool,
   pub runtime_pkg: String,
   pub jsx_import_source: Option<String>,
   pub filename: String,
   /// @internal
-  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED'")]
+  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED' | 'SSR'")]
   pub target: TransformTarget,
   /// @internal
   pub is_dynamic_component: Option<bool>,
+  /// @public
+  /// Use the structural fingerprint of a snapshot as its uid, so that the identical snapshots of
+  /// concatenated modules can be merged by `transformBundleResult` with `dedupeSnapshots`.
+  pub fingerprint_uid: Option<bool>,
+  /// @public
+  /// The name of the entry, which is a part of the snapshot uids.
+  pub entry_name: Option<String>,
+  /// @public
+  /// The custom native elements with dedicated creators, by tag name.
+  #[napi(ts_type = "Record<string, ElementConfig>")]
+  pub elements: Option<HashMap<String, ElementConfig>>,
+  /// @public
+  /// How `<component>` is created.
+  pub component: Option<ComponentConfig>,
+  /// @public
+  /// Attach the source location of the JSX to each created element in development mode, e.g.:
+  /// `{ file: "src/App.jsx", line: 3, column: 5, component: "App" }`, as `__lynx_source_location`.
+  pub source_locations: Option<bool>,
 }
 
 impl Default for JSXTransformerConfig {
@@ -1106,16 +1683,30 @@ impl Default for JSXTransformerConfig {
       filename: Default::default(),
       target: TransformTarget::LEPUS,
       is_dynamic_component: Some(false),
+      fingerprint_uid: None,
+      entry_name: None,
+      elements: None,
+      component: None,
+      source_locations: None,
     }
   }
 }
 
+/// A component, or a snapshot, in which the nested snapshots are numbered.
+struct SnapshotScope {
+  path: String,
+  count: u32,
+  /// The name of the function, class or variable, `None` for a snapshot.
+  name: Option<String>,
+}
+
 pub struct JSXTransformer<C>
 where
   C: Comments + Clone,
 {
   // react_transformer: Box<dyn Fold>,
   cfg: JSXTransformerConfig,
+  cm: Lrc<SourceMapperDyn>,
   filename_hash: String,
   content_hash: String,
   unresolved_mark: Mark,
@@ -1126,7 +1717,17 @@ where
   snapshot_counter: u32,
   current_snapshot_defs: Vec<ModuleItem>,
   current_snapshot_id: Option<Ident>,
+  /// The snapshot definitions of the module by fingerprint.
+  snapshot_ids: HashMap<String, Ident>,
+  /// The path of the current component and snapshot, used by the uids of development mode.
+  snapshot_scopes: Vec<SnapshotScope>,
+  mode: TransformMode,
+  /// The module level constants, which are folded into the static parts of the snapshots.
+  const_evaluator: Rc<ConstEvaluator>,
+  registry: Rc<ElementRegistry>,
+  pragmas: Option<Pragmas>,
   comments: Option<C>,
+  pub manifest: Vec<SnapshotManifest>,
 }
 
 impl<C> JSXTransformer<C>
@@ -1138,16 +1739,29 @@ where
     self
   }
 
+  /// Use the pragmas already parsed by the caller instead of parsing them again.
+  pub fn with_pragmas(mut self, pragmas: Pragmas) -> Self {
+    self.pragmas = Some(pragmas);
+    self
+  }
+
   pub fn new(
     cfg: JSXTransformerConfig,
-    _cm: Lrc<SourceMap>,
+    cm: Lrc<SourceMapperDyn>,
     comments: Option<C>,
     _top_level_mark: Mark,
     unresolved_mark: Mark,
     mode: TransformMode,
   ) -> Self {
+    let registry = Rc::new(ElementRegistry {
+      elements: cfg.elements.clone().unwrap_or_default(),
+      component: cfg.component.clone().unwrap_or_default(),
+    });
     JSXTransformer {
-      filename_hash: calc_hash(&cfg.filename.clone()),
+      filename_hash: match &cfg.entry_name {
+        Some(entry_name) => calc_hash(&format!("{}:{}", entry_name, cfg.filename)),
+        None => calc_hash(&cfg.filename.clone()),
+      },
       content_hash: "test".into(),
       runtime_id: match mode {
         TransformMode::Development => {
@@ -1161,60 +1775,97 @@ where
       runtime_components_ident: private_ident!("ReactLynxRuntimeComponents"),
       runtime_components_module_item: None,
       cfg,
+      cm,
       css_id_value: None,
       unresolved_mark,
       snapshot_counter: 0,
       current_snapshot_defs: vec![],
       current_snapshot_id: None,
+      snapshot_ids: HashMap::new(),
+      snapshot_scopes: vec![SnapshotScope {
+        path: String::new(),
+        count: 0,
+        name: None,
+      }],
+      mode,
+      const_evaluator: Default::default(),
+      registry,
+      pragmas: None,
       comments,
+      manifest: vec![],
     }
   }
 
-  fn parse_directives(&mut self, span: Span) {
-    self.comments.with_leading(span.lo, |comments| {
-      for cmt in comments {
-        if cmt.kind != CommentKind::Block {
-          continue;
-        }
-        for line in cmt.text.lines() {
-          let mut line = line.trim();
-          if line.starts_with('*') {
-            line = line[1..].trim();
-          }
-
-          if !line.starts_with("@jsx") {
-            continue;
-          }
+  fn push_scope(&mut self, name: &str) {
+    let parent = self.snapshot_scopes.last().unwrap();
+    let path = if parent.path.is_empty() {
+      name.to_string()
+    } else {
+      format!("{}_{}", parent.path, name)
+    };
+    self.snapshot_scopes.push(SnapshotScope {
+      path,
+      count: 0,
+      name: Some(name.to_string()),
+    });
+  }
 
-          let mut words = line.split_whitespace();
-          loop {
-            let pragma = words.next();
-            if pragma.is_none() {
-              break;
-            }
-            let val = words.next();
-            match pragma {
-              // Some("@jsxImportSource") => {
-              //   if let Some(src) = val {
-              //     self.cfg.runtime_pkg = src.into();
-              //   }
-              // }
-              Some("@jsxCSSId") => {
-                if let Some(css_id) = val {
-                  self.css_id_value = Some(Expr::Lit(Lit::Num(
-                    css_id
-                      .parse::<f64>()
-                      .expect("should have numeric cssId")
-                      .into(),
-                  )));
-                }
-              }
-              _ => {}
-            }
-          }
-        }
-      }
+  /// Number the snapshot in the current scope, e.g.: `App_0`, and enter it.
+  fn push_snapshot_scope(&mut self) -> String {
+    let parent = self.snapshot_scopes.last_mut().unwrap();
+    let path = if parent.path.is_empty() {
+      parent.count.to_string()
+    } else {
+      format!("{}_{}", parent.path, parent.count)
+    };
+    parent.count += 1;
+    self.snapshot_scopes.push(SnapshotScope {
+      path: path.clone(),
+      count: 0,
+      name: None,
     });
+    path
+  }
+
+  /// The innermost component of the current scope, or the innermost function if there is none.
+  fn component_name(&self) -> Option<&str> {
+    let mut names = self
+      .snapshot_scopes
+      .iter()
+      .rev()
+      .filter_map(|scope| scope.name.as_deref());
+    names
+      .clone()
+      .find(|name| name.starts_with(|c: char| c.is_ascii_uppercase()))
+      .or_else(|| names.next())
+  }
+
+  /// `__SetAttribute(el, "__lynx_source_location", { file, line, column, component })` of each
+  /// element.
+  fn source_location_stmts(&self, element_spans: Vec<(Ident, Span)>) -> Vec<Stmt> {
+    let component = match self.component_name() {
+      Some(name) => Expr::Lit(Lit::Str(name.into())),
+      None => Expr::Ident("undefined".into()),
+    };
+    element_spans
+      .into_iter()
+      .map(|(el, span)| {
+        let loc = self.cm.lookup_char_pos(span.lo);
+        quote!(
+          r#"__SetAttribute($element, "__lynx_source_location", {
+            file: $file,
+            line: $line,
+            column: $column,
+            component: $component
+          })"# as Stmt,
+          element = el,
+          file: Expr = Expr::Lit(Lit::Str(self.cfg.filename.as_str().into())),
+          line: Expr = Expr::Lit(Lit::Num(loc.line.into())),
+          column: Expr = Expr::Lit(Lit::Num((loc.col_display + 1).into())),
+          component: Expr = component.clone(),
+        )
+      })
+      .collect()
   }
 }
 
@@ -1223,6 +1874,7 @@ where
   C: Comments + Clone,
 {
   fn visit_mut_jsx_element(&mut self, node: &mut JSXElement) {
+    record_span(node.span);
     match *jsx_name(node.opening.name.clone()) {
       Expr::Lit(lit) => {
         if let Lit::Str(s) = &lit {
@@ -1260,14 +1912,6 @@ where
             }
             return node.visit_mut_children_with(self);
           }
-
-          if s.value.as_ref() == "component" {
-            HANDLER.with(|handler| {
-              handler
-                .struct_span_err(node.opening.name.span(), "<component /> is not supported")
-                .emit()
-            });
-          }
         }
       }
       _ => {
@@ -1275,18 +1919,18 @@ where
       }
     }
 
+    if !self.registry.elements.is_empty() {
+      node.visit_with(&mut ElementChildrenValidator {
+        elements: &self.registry.elements,
+      });
+    }
+
+    // Reserve the counter before the nested snapshots, it is skipped if the snapshot is deduped.
     self.snapshot_counter += 1;
+    let snapshot_counter = self.snapshot_counter;
+    let snapshot_path = self.push_snapshot_scope();
 
-    let snapshot_uid = format!(
-      "__snapshot_{}_{}_{}",
-      self.filename_hash, self.content_hash, self.snapshot_counter
-    );
-    let snapshot_id = Ident::new(
-      // format!("__snapshot_{}", snapshot_uid).into(),
-      snapshot_uid.clone().into(),
-      DUMMY_SP,
-      SyntaxContext::default().apply_mark(Mark::fresh(Mark::root())),
-    );
+    node.visit_mut_with(&mut SwitchMarker);
 
     let mut wrap_dynamic_part = WrapperMarker {
       current_is_children_full_dynamic: false,
@@ -1299,16 +1943,23 @@ where
     let mut dynamic_part_extractor = DynamicPartExtractor::new(
       self.runtime_id.clone(),
       wrap_dynamic_part.dynamic_part_count,
+      self.const_evaluator.clone(),
+      self.registry.clone(),
       self,
     );
 
     node.visit_mut_with(&mut dynamic_part_extractor);
 
+    let snapshot_elements = dynamic_part_extractor.element_manifest_tree(None);
+
     let mut snapshot_values: Vec<Option<ExprOrSpread>> = vec![];
     let mut snapshot_values_has_attr = false;
     let mut snapshot_attrs: Vec<JSXAttrOrSpread> = vec![];
     let mut snapshot_children: Vec<JSXElementChild> = vec![];
     let mut snapshot_dynamic_part_def: Vec<Option<ExprOrSpread>> = vec![];
+    // The updaters of the main thread, which are always used for the fingerprint so that both
+    // threads dedupe the snapshots in the same way.
+    let mut fingerprint_dynamic_part_def: Vec<Option<ExprOrSpread>> = vec![];
     let mut snapshot_slot_def: Vec<Option<ExprOrSpread>> = vec![];
 
     match dynamic_part_extractor.key {
@@ -1326,12 +1977,29 @@ where
       .dynamic_parts
       .into_iter()
       .partition(|dynamic_part| match dynamic_part {
-        DynamicPart::Attr(_, _, _) | DynamicPart::Spread(_, _) => true,
+        DynamicPart::Attr(_, _, _) | DynamicPart::Spread(..) | DynamicPart::Switch(..) => true,
         DynamicPart::Slot(_, _) | DynamicPart::Children(_, _) | DynamicPart::ListChildren(_, _) => {
           false
         }
       });
 
+    let snapshot_dynamic_parts_manifest = dynamic_part_attr
+      .iter()
+      .chain(dynamic_part_children.iter())
+      .map(DynamicPart::to_manifest)
+      .collect::<Vec<_>>();
+    let mut snapshot_creator_fn = dynamic_part_extractor.snapshot_creator.unwrap();
+    let element_spans = dynamic_part_extractor.element_spans;
+    let snapshot_renderer = (target == TransformTarget::SSR).then(|| {
+      SnapshotRenderer::new(
+        &self.registry,
+        &snapshot_creator_fn,
+        &dynamic_part_attr,
+        &dynamic_part_children,
+      )
+      .render(&snapshot_elements)
+    });
+
     dynamic_part_attr
       .into_iter()
       .enumerate()
@@ -1368,7 +2036,7 @@ where
       .for_each(
         |(_name, _child_name, _jsx_opening, _jsx_closing, dynamic_part)| {
           match &dynamic_part {
-            DynamicPart::Attr(_, _, _) | DynamicPart::Spread(_, _) => {
+            DynamicPart::Attr(_, _, _) | DynamicPart::Spread(..) | DynamicPart::Switch(..) => {
               snapshot_dynamic_part_def.push(Some(ExprOrSpread {
                 spread: None,
                 expr: Box::new((&dynamic_part).to_updater(
@@ -1377,6 +2045,14 @@ where
                   snapshot_dynamic_part_def.len() as i32,
                 )),
               }));
+              fingerprint_dynamic_part_def.push(Some(ExprOrSpread {
+                spread: None,
+                expr: Box::new(dynamic_part.to_updater(
+                  runtime_id.clone(),
+                  TransformTarget::LEPUS,
+                  fingerprint_dynamic_part_def.len() as i32,
+                )),
+              }));
             }
             DynamicPart::Slot(_, _) => {}
             DynamicPart::Children(_, _) => {}
@@ -1421,7 +2097,7 @@ where
               //   })),
               // }));
             }
-            DynamicPart::Spread(value, _) => {
+            DynamicPart::Spread(value, ..) | DynamicPart::Switch(value, ..) => {
               snapshot_values.push(Some(ExprOrSpread {
                 spread: None,
                 expr: Box::new(value),
@@ -1464,7 +2140,8 @@ where
         dynamic_part_children.into_iter().for_each(|dynamic_part| {
           match dynamic_part {
             DynamicPart::Attr(_, _, _) => {}
-            DynamicPart::Spread(_, _) => {}
+            DynamicPart::Spread(..) => {}
+            DynamicPart::Switch(..) => {}
             DynamicPart::ListChildren(expr, element_index) => {
               // snapshot_values.push(None);
               snapshot_children.push(match expr {
@@ -1523,43 +2200,118 @@ where
       }
     };
 
-    let snapshot_creator = if target == TransformTarget::JS {
-      Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
-    } else {
-      Expr::Fn(FnExpr {
-        ident: None,
-        function: Box::new(dynamic_part_extractor.snapshot_creator.unwrap()),
-      })
+    self.snapshot_scopes.pop();
+
+    let css_id = match &self.css_id_value {
+      Some(css_id_expr) => css_id_expr.clone(),
+      // We use `undefined` here since runtime will skip `__SetCSSId` when `cssId === undefined && entryName === undefined`
+      None => Expr::Ident("undefined".into()),
     };
 
-    let mut snapshot_create_call = quote!(
-        r#"$runtime_id.createSnapshot(
-             $snapshot_uid,
-             $snapshot_creator,
-             $snapshot_dynamic_parts_def,
-             $slot,
-             $css_id,
-             globDynamicComponentEntry
-        )"# as Expr,
-        runtime_id: Expr = self.runtime_id.clone(),
-        // FIXME(colinaaa): Use snapshot_uid with entry_name
-        snapshot_uid: Expr = Expr::Lit(Lit::Str(snapshot_uid.into())),
-        snapshot_creator: Expr = snapshot_creator,
-        snapshot_dynamic_parts_def: Expr = match (target, snapshot_dynamic_part_def.len()) {
-          (TransformTarget::JS, _) | (_, 0) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
-          _ => Expr::Array(ArrayLit { span: DUMMY_SP, elems: snapshot_dynamic_part_def }),
-        },
-        slot: Expr = slot_expr,
-        css_id: Expr = match &self.css_id_value {
-          Some(css_id_expr) => css_id_expr.clone(),
-          // We use `undefined` here since runtime will skip `__SetCSSId` when `cssId === undefined && entryName === undefined`
-          None => Expr::Ident("undefined".into()),
+    let fingerprint = snapshot_fingerprint(
+      &snapshot_creator_fn,
+      fingerprint_dynamic_part_def,
+      &slot_expr,
+      &css_id,
+    );
+
+    // The source locations are left out of the fingerprint, so that the uids of development mode
+    // stay the same when the JSX moves. But the snapshots at different locations are not merged.
+    let mut dedupe_key = fingerprint.clone();
+    if self.mode == TransformMode::Development
+      && matches!(self.cfg.source_locations, Some(true))
+      && !matches!(target, TransformTarget::JS | TransformTarget::SSR)
+    {
+      let stmts = self.source_location_stmts(element_spans);
+      dedupe_key.push_str(&calc_hash(&stmts.iter().map(to_code).collect::<String>()));
+      if let Some(body) = &mut snapshot_creator_fn.body {
+        let ret = body.stmts.pop();
+        body.stmts.extend(stmts);
+        body.stmts.extend(ret);
+      }
+    }
+
+    // Structurally identical snapshots of the module share one definition.
+    if let Some(snapshot_id) = self.snapshot_ids.get(&dedupe_key) {
+      let snapshot_id = snapshot_id.clone();
+      self.current_snapshot_id = Some(snapshot_id.clone());
+      *node = snapshot_jsx_element(
+        node.span,
+        snapshot_id,
+        snapshot_attrs,
+        snapshot_values_has_attr.then_some(snapshot_values),
+        snapshot_children,
+        wrap_dynamic_part.dynamic_part_count == 0,
+      );
+      return;
+    }
+
+    let snapshot_uid = if matches!(self.cfg.fingerprint_uid, Some(true)) {
+      format!("__snapshot_{}", fingerprint)
+    } else if self.mode == TransformMode::Development {
+      // Stable across the unrelated edits of the file, so that HMR only re-registers the
+      // snapshots whose structure changed.
+      format!(
+        "__snapshot_{}_{}_{}",
+        self.filename_hash,
+        snapshot_path,
+        &fingerprint[..8]
+      )
+    } else {
+      format!(
+        "__snapshot_{}_{}_{}",
+        self.filename_hash, self.content_hash, snapshot_counter
+      )
+    };
+    let snapshot_id = Ident::new(
+      snapshot_uid.clone().into(),
+      DUMMY_SP,
+      SyntaxContext::default().apply_mark(Mark::fresh(Mark::root())),
+    );
+    self.snapshot_ids.insert(dedupe_key, snapshot_id.clone());
+
+    let snapshot_manifest = SnapshotManifest {
+      uid: snapshot_uid.clone(),
+      fingerprint,
+      elements: snapshot_elements,
+      dynamic_parts: snapshot_dynamic_parts_manifest,
+    };
+
+    let snapshot_creator = if matches!(target, TransformTarget::JS | TransformTarget::SSR) {
+      Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
+    } else {
+      Expr::Fn(FnExpr {
+        ident: None,
+        function: Box::new(snapshot_creator_fn),
+      })
+    };
+
+    let mut snapshot_create_call = quote!(
+        r#"$runtime_id.createSnapshot(
+             $snapshot_uid,
+             $snapshot_creator,
+             $snapshot_dynamic_parts_def,
+             $slot,
+             $css_id,
+             globDynamicComponentEntry
+        )"# as Expr,
+        runtime_id: Expr = self.runtime_id.clone(),
+        snapshot_uid: Expr = Expr::Lit(Lit::Str(snapshot_uid.into())),
+        snapshot_creator: Expr = snapshot_creator,
+        snapshot_dynamic_parts_def: Expr = match (target, snapshot_dynamic_part_def.len()) {
+          (TransformTarget::JS | TransformTarget::SSR, _) | (_, 0) => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+          _ => Expr::Array(ArrayLit { span: DUMMY_SP, elems: snapshot_dynamic_part_def }),
         },
+        slot: Expr = slot_expr,
+        css_id: Expr = css_id,
         // has_multi_children: Expr = Expr::Lit(Lit::Num(Number { span: DUMMY_SP, value: wrap_dynamic_part.dynamic_part_count as f64, raw: None })),
     );
 
     snapshot_create_call = match snapshot_create_call {
       Expr::Call(mut call) => {
+        if let Some(snapshot_renderer) = snapshot_renderer {
+          call.args.push(snapshot_renderer.into());
+        }
         let pure_span = Span::dummy_with_cmt();
         self.comments.add_pure_comment(pure_span.lo);
         call.span = pure_span;
@@ -1577,41 +2329,60 @@ where
 
     self.current_snapshot_id = Some(snapshot_id.clone());
     self.current_snapshot_defs.push(snapshot_def);
+    self.manifest.push(snapshot_manifest);
+
+    *node = snapshot_jsx_element(
+      node.span,
+      snapshot_id,
+      snapshot_attrs,
+      snapshot_values_has_attr.then_some(snapshot_values),
+      snapshot_children,
+      wrap_dynamic_part.dynamic_part_count == 0,
+    );
+  }
 
-    *node = JSXElement {
-      span: node.span(),
-      opening: JSXOpeningElement {
-        name: JSXElementName::Ident(snapshot_id.clone()),
-        span: node.span,
-        attrs: {
-          if snapshot_values_has_attr {
-            snapshot_attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
-              span: DUMMY_SP,
-              name: JSXAttrName::Ident(IdentName::new("values".into(), DUMMY_SP)),
-              value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
-                span: DUMMY_SP,
-                expr: JSXExpr::Expr(Box::new(Expr::Array(ArrayLit {
-                  span: DUMMY_SP,
-                  elems: snapshot_values,
-                }))),
-              })),
-            }))
-          };
-          snapshot_attrs
-        },
-        self_closing: wrap_dynamic_part.dynamic_part_count == 0,
-        type_args: None,
-      },
-      children: snapshot_children,
-      closing: if wrap_dynamic_part.dynamic_part_count == 0 {
-        None
-      } else {
-        Some(JSXClosingElement {
-          name: JSXElementName::Ident(snapshot_id.clone()),
-          span: DUMMY_SP,
-        })
-      },
+  fn visit_mut_fn_decl(&mut self, n: &mut FnDecl) {
+    self.push_scope(&n.ident.sym);
+    n.visit_mut_children_with(self);
+    self.snapshot_scopes.pop();
+  }
+
+  fn visit_mut_class_decl(&mut self, n: &mut ClassDecl) {
+    self.push_scope(&n.ident.sym);
+    n.visit_mut_children_with(self);
+    self.snapshot_scopes.pop();
+  }
+
+  fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+    match &n.name {
+      Pat::Ident(name) => {
+        self.push_scope(&name.sym.clone());
+        n.visit_mut_children_with(self);
+        self.snapshot_scopes.pop();
+      }
+      _ => n.visit_mut_children_with(self),
+    }
+  }
+
+  fn visit_mut_export_default_decl(&mut self, n: &mut ExportDefaultDecl) {
+    let name = match &n.decl {
+      DefaultDecl::Fn(FnExpr {
+        ident: Some(ident), ..
+      })
+      | DefaultDecl::Class(ClassExpr {
+        ident: Some(ident), ..
+      }) => ident.sym.to_string(),
+      _ => "default".into(),
     };
+    self.push_scope(&name);
+    n.visit_mut_children_with(self);
+    self.snapshot_scopes.pop();
+  }
+
+  fn visit_mut_export_default_expr(&mut self, n: &mut ExportDefaultExpr) {
+    self.push_scope("default");
+    n.visit_mut_children_with(self);
+    self.snapshot_scopes.pop();
   }
 
   fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
@@ -1633,16 +2404,25 @@ where
   }
 
   fn visit_mut_module(&mut self, n: &mut Module) {
-    self.parse_directives(n.span);
-    for item in &n.body {
-      let span = item.span();
-      self.parse_directives(span);
+    let pragmas = match self.pragmas.take() {
+      Some(pragmas) => pragmas,
+      None => Pragmas::parse(
+        &self.comments,
+        iter::once(n.span).chain(n.body.iter().map(|item| item.span())),
+      ),
+    };
+    if let Some(css_id) = pragmas.jsx_css_id {
+      self.css_id_value = Some(Expr::Lit(Lit::Num(css_id.into())));
+    }
+    if let Some(source) = pragmas.jsx_import_source {
+      self.cfg.runtime_pkg = source;
     }
 
     if matches!(self.cfg.is_dynamic_component, Some(true)) && self.css_id_value == None {
       self.css_id_value = Some(Expr::Lit(Lit::Num(0.into())));
     }
 
+    self.const_evaluator = Rc::new(ConstEvaluator::from_module(n));
     n.visit_mut_children_with(self);
     match Lazy::<Expr>::get(&self.runtime_id) {
       Some(runtime_id) => {
@@ -1703,22 +2483,10 @@ where
   }
 }
 
-// #[plugin_transform]
-// pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-//     let filename = metadata
-//         .get_context(&TransformPluginMetadataContextKind::Filename)
-//         .unwrap();
-
-//     program.fold_with(&mut JSXTransformer {
-//         // filename: "index.js".into(),
-//         filename,
-//         snapshot_counter: 0,
-//         current_snapshot_defs: vec![],
-//     })
-// }
-
 #[cfg(test)]
 mod tests {
+  use std::collections::HashMap;
+
   use swc_core::{
     common::{comments::SingleThreadedComments, Mark},
     ecma::{
@@ -1729,7 +2497,10 @@ mod tests {
   };
 
   use crate::target::TransformTarget;
-  use crate::{swc_plugin_snapshot::JSXTransformer, TransformMode};
+  use crate::{
+    swc_plugin_snapshot::{ComponentConfig, ElementChildren, ElementConfig, JSXTransformer},
+    TransformMode,
+  };
 
   test!(
     module,
@@ -2324,6 +3095,292 @@ mod tests {
     "#
   );
 
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: true,
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+      )
+    },
+    should_fold_constants,
+    // Input codes
+    r#"
+    const BASE = 50;
+    export const PREFIX = 'a';
+    let mutable = 'b';
+    <view style={{ width: BASE * 2 + 'px', height: h, flexShrink: -(BASE / 100) }} />;
+    <view style={`width: ${BASE}rpx;`} className={`${PREFIX}-${BASE}`} id={PREFIX + 1} data-base={BASE} />;
+    <view className={`${PREFIX} ${mutable}`} />;
+    function App(BASE) {
+      return <view style={{ width: BASE + 'px' }} />;
+    }
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: true,
+            elements: Some(HashMap::from([
+              (
+                "map".into(),
+                ElementConfig {
+                  creator: "__CreateMap".into(),
+                  creation_attributes: Some(vec!["provider".into(), "lite-mode".into()]),
+                  children: Some(ElementChildren::None),
+                },
+              ),
+              (
+                "rich-text".into(),
+                ElementConfig {
+                  creator: "__CreateRichText".into(),
+                  children: Some(ElementChildren::Text),
+                  ..Default::default()
+                },
+              ),
+            ])),
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+      )
+    },
+    should_create_registered_elements,
+    // Input codes
+    r#"
+    const PROVIDER = 'amap';
+    <view>
+      <map provider={PROVIDER} lite-mode zoom={zoom} />
+      <map {...props} provider="amap" />
+      <rich-text>Hello, {name}</rich-text>
+    </view>;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: true,
+            component: Some(ComponentConfig {
+              path_attribute: Some("src".into()),
+              ..Default::default()
+            }),
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+      )
+    },
+    should_create_component,
+    // Input codes
+    r#"
+    const NAME = 'header';
+    <view>
+      <component name={NAME} src="./header" class="a">
+        <text>{title}</text>
+      </component>
+      <component name={name} />
+      <component />
+    </view>;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: true,
+            target: TransformTarget::SSR,
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+      )
+    },
+    should_render_snapshot_ssr,
+    // Input codes
+    r#"
+    <view class="a" id="b" style={{ width: '100px', color: 'red' }} data-x="1">
+      <text>Hello & "world"</text>
+      <image src={src} className={cls} style={{ height: h, 9999: w }} bindtap={onTap} />
+      <view {...props} class="c" />
+      {child}
+      <text>{name}</text>
+    </view>;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: true,
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+      )
+    },
+    should_compile_static_conditional_slots,
+    // Input codes
+    r#"
+    <view>
+      {loading ? <text class="loading">Loading</text> : <view><text>Done</text></view>}
+      {count > 0 && <text>Has items</text>}
+      {(error ? null : <image src="ok.png" />)}
+      {visible && <text>Not boolean</text>}
+      {loading ? <text>{title}</text> : <text>Dynamic</text>}
+      {loading ? <Spinner /> : null}
+    </view>;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let unresolved_mark = Mark::new();
+      let top_level_mark = Mark::new();
+
+      (
+        resolver(unresolved_mark, top_level_mark, true),
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: true,
+            target: TransformTarget::SSR,
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+      )
+    },
+    should_render_static_conditional_slots_ssr,
+    // Input codes
+    r#"
+    <view>
+      {loading ? <text class="loading">Loading</text> : <view><text>Done</text></view>}
+      {count > 0 && <text>Has items</text>}
+    </view>;
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| visit_mut_pass(JSXTransformer::new(
+      super::JSXTransformerConfig {
+        preserve_jsx: true,
+        filename: "src/App.jsx".into(),
+        source_locations: Some(true),
+        ..Default::default()
+      },
+      t.cm.clone(),
+      Some(t.comments.clone()),
+      Mark::new(),
+      Mark::new(),
+      TransformMode::Development
+    )),
+    should_attach_source_locations_in_development,
+    // Input codes
+    r#"
+    function App() {
+      const items = list.map(item => <text>{item}</text>);
+      return (
+        <view>
+          <text>{title}</text>
+        </view>
+      );
+    }
+    const Footer = () => <view><text>{title}</text></view>;
+    "#
+  );
+
   test!(
     module,
     Syntax::Es(EsSyntax {
@@ -2489,6 +3546,60 @@ mod tests {
     "#
   );
 
+  test!(
+    module,
+    Syntax::Es(EsSyntax {
+      jsx: true,
+      ..Default::default()
+    }),
+    |t| {
+      let top_level_mark = Mark::new();
+      let unresolved_mark = Mark::new();
+      (
+        visit_mut_pass(JSXTransformer::<&SingleThreadedComments>::new(
+          super::JSXTransformerConfig {
+            preserve_jsx: false,
+            ..Default::default()
+          },
+          t.cm.clone(),
+          None,
+          top_level_mark,
+          unresolved_mark,
+          TransformMode::Test,
+        )),
+        react::react::<&SingleThreadedComments>(
+          t.cm.clone(),
+          None,
+          react::Options {
+            next: Some(false),
+            runtime: Some(react::Runtime::Automatic),
+            import_source: Some("@lynx-js/react".into()),
+            pragma: None,
+            pragma_frag: None,
+            throw_if_namespace: None,
+            development: Some(false),
+            refresh: None,
+            ..Default::default()
+          },
+          top_level_mark,
+          unresolved_mark,
+        ),
+      )
+    },
+    spread_with_attributes_after,
+    // Input codes
+    r#"
+    <view>
+      <view {...a} class="foo" {...b} className={cls} style={{ color: "red" }} data-x="x" id={id} bindtap={onTap} ref={ref}>
+        <text {...c} main-thread:bindtap={onMTTap}>!!!</text>
+      </view>
+      <list>
+        <list-item {...d} item-key={key} name="bar" />
+      </list>
+    </view>
+    "#
+  );
+
   test!(
     module,
     Syntax::Es(EsSyntax {
@@ -9,12 +9,13 @@ use swc_core::{
 use super::{
   jsx_helpers::{
     jsx_children_to_expr, jsx_has_dynamic_key, jsx_is_children_full_dynamic, jsx_is_custom,
-    jsx_is_list, jsx_name, jsx_text_to_str,
+    jsx_is_list, jsx_is_static, jsx_name, jsx_text_to_str,
   },
   WRAPPER_NODE_2,
 };
 
 pub static INTERNAL_SLOT_STR: &str = "internal-slot";
+pub static INTERNAL_SWITCH_STR: &str = "internal-switch";
 
 pub fn jsx_is_internal_slot(jsx: &JSXElement) -> bool {
   match *jsx_name(jsx.opening.name.clone()) {
@@ -32,6 +33,13 @@ pub fn jsx_unwrap_internal_slot(mut jsx: JSXElement) -> JSXElement {
   unreachable!("unwrap_internal_slot");
 }
 
+pub fn jsx_is_internal_switch(jsx: &JSXElement) -> bool {
+  match *jsx_name(jsx.opening.name.clone()) {
+    Expr::Lit(Lit::Str(s)) => s.value.as_ref() == INTERNAL_SWITCH_STR,
+    _ => false,
+  }
+}
+
 fn jsx_wrapped(with: &str, n: JSXElement) -> JSXElement {
   JSXElement {
     span: DUMMY_SP,
@@ -50,6 +58,148 @@ fn jsx_wrapped(with: &str, n: JSXElement) -> JSXElement {
   }
 }
 
+// Mark the conditional children whose branches are static JSX, e.g.: `{cond ? <a/> : <b/>}` and
+// `{cond && <a/>}`, as `<internal-switch condition={cond}><a/><b/></internal-switch>`.
+// The first branch is shown when the condition is truthy, and the second one (if any) otherwise.
+// The switch is a static child for the `WrapperMarker`, so the branches are created by the snapshot
+// instead of being separate snapshots.
+pub struct SwitchMarker;
+
+impl SwitchMarker {
+  fn switch_of(expr: &Expr) -> Option<(Expr, Vec<JSXElement>)> {
+    match expr {
+      Expr::Paren(ParenExpr { expr, .. }) => Self::switch_of(expr),
+      Expr::Cond(CondExpr {
+        test, cons, alt, ..
+      }) => match (static_branch(cons)?, static_branch(alt)?) {
+        (Some(cons), alt) => Some((*test.clone(), [cons].into_iter().chain(alt).collect())),
+        (None, Some(alt)) => Some((
+          Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: UnaryOp::Bang,
+            arg: Box::new(paren_if_needed(*test.clone())),
+          }),
+          vec![alt],
+        )),
+        (None, None) => None,
+      },
+      // `cond && <a/>` renders `cond` when it is falsy, so only the boolean conditions are switches.
+      Expr::Bin(BinExpr {
+        op: BinaryOp::LogicalAnd,
+        left,
+        right,
+        ..
+      }) if is_boolean(left) => static_branch(right)?.map(|right| (*left.clone(), vec![right])),
+      _ => None,
+    }
+  }
+}
+
+impl VisitMut for SwitchMarker {
+  fn visit_mut_jsx_element(&mut self, n: &mut JSXElement) {
+    // The children of custom elements and lists are not created by the snapshot.
+    if jsx_is_custom(n) || jsx_is_list(n) {
+      return;
+    }
+
+    for child in n.children.iter_mut() {
+      match child {
+        JSXElementChild::JSXElement(element) => element.visit_mut_with(self),
+        JSXElementChild::JSXExprContainer(JSXExprContainer {
+          expr: JSXExpr::Expr(expr),
+          ..
+        }) => {
+          if let Some((condition, branches)) = Self::switch_of(expr) {
+            *child = JSXElementChild::JSXElement(Box::new(jsx_switch(condition, branches)));
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
+/// `Some(None)` for the branches rendering nothing, e.g.: `null`.
+fn static_branch(expr: &Expr) -> Option<Option<JSXElement>> {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => static_branch(expr),
+    Expr::JSXElement(jsx) if jsx_is_static(jsx) => Some(Some(*jsx.clone())),
+    Expr::Lit(Lit::Null(_)) | Expr::Lit(Lit::Bool(Bool { value: false, .. })) => Some(None),
+    Expr::Ident(ident) if ident.sym == "undefined" => Some(None),
+    _ => None,
+  }
+}
+
+/// Whether `expr` always evaluates to a boolean.
+pub fn is_boolean(expr: &Expr) -> bool {
+  match expr {
+    Expr::Paren(ParenExpr { expr, .. }) => is_boolean(expr),
+    Expr::Lit(Lit::Bool(_)) => true,
+    Expr::Unary(UnaryExpr {
+      op: UnaryOp::Bang, ..
+    }) => true,
+    Expr::Bin(BinExpr {
+      op: BinaryOp::LogicalAnd | BinaryOp::LogicalOr,
+      left,
+      right,
+      ..
+    }) => is_boolean(left) && is_boolean(right),
+    Expr::Bin(BinExpr { op, .. }) => matches!(
+      op,
+      BinaryOp::EqEq
+        | BinaryOp::NotEq
+        | BinaryOp::EqEqEq
+        | BinaryOp::NotEqEq
+        | BinaryOp::Lt
+        | BinaryOp::LtEq
+        | BinaryOp::Gt
+        | BinaryOp::GtEq
+        | BinaryOp::In
+        | BinaryOp::InstanceOf
+    ),
+    _ => false,
+  }
+}
+
+pub fn paren_if_needed(expr: Expr) -> Expr {
+  match expr {
+    Expr::Ident(_) | Expr::Member(_) | Expr::Call(_) | Expr::Lit(_) | Expr::Paren(_) => expr,
+    _ => Expr::Paren(ParenExpr {
+      span: DUMMY_SP,
+      expr: Box::new(expr),
+    }),
+  }
+}
+
+fn jsx_switch(condition: Expr, branches: Vec<JSXElement>) -> JSXElement {
+  let name = JSXElementName::Ident(IdentName::new(INTERNAL_SWITCH_STR.into(), DUMMY_SP).into());
+  JSXElement {
+    span: DUMMY_SP,
+    opening: JSXOpeningElement {
+      span: DUMMY_SP,
+      name: name.clone(),
+      attrs: vec![JSXAttrOrSpread::JSXAttr(JSXAttr {
+        span: DUMMY_SP,
+        name: JSXAttrName::Ident(IdentName::new("condition".into(), DUMMY_SP)),
+        value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+          span: DUMMY_SP,
+          expr: JSXExpr::Expr(Box::new(condition)),
+        })),
+      })],
+      self_closing: false,
+      type_args: None,
+    },
+    closing: Some(JSXClosingElement {
+      span: DUMMY_SP,
+      name,
+    }),
+    children: branches
+      .into_iter()
+      .map(|branch| JSXElementChild::JSXElement(Box::new(branch)))
+      .collect(),
+  }
+}
+
 // Wrap dynamic part with wrapper node (or if it's children is full dynamic, do nothing)
 // after this pass, all dynamic part will be wrapped with wrapper node
 pub struct WrapperMarker {
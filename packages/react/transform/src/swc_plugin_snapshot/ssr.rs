@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use swc_core::{common::DUMMY_SP, ecma::ast::*, quote};
+
+use super::{
+  attr_name::AttrName, const_eval::ConstValue, element::ElementRegistry, i32_to_expr, DynamicPart,
+  SnapshotElementManifest,
+};
+use crate::css_property::CSS_PROPERTY_MAP;
+
+static CSS_PROPERTY_NAMES: Lazy<HashMap<u32, &'static str>> = Lazy::new(|| {
+  CSS_PROPERTY_MAP
+    .iter()
+    .map(|(name, id)| (*id, name.as_str()))
+    .collect()
+});
+
+/// The static attributes of an element, read from the Element PAPI calls of the snapshot creator.
+#[derive(Default)]
+struct StaticAttrs {
+  attrs: Vec<(String, String)>,
+  style: Vec<String>,
+}
+
+/// Generates the function that renders a snapshot to markup for `TransformTarget::SSR`.
+///
+/// ```js
+/// function (ctx, r) {
+///   return "<view class=\"a\"" + r.attr("id", ctx.__values[0]) + ">" + r.slot(ctx, 0) + "</view>";
+/// }
+/// ```
+///
+/// The static elements are serialized at compile time. The dynamic parts are interpolated with
+/// the same indices as the updaters and the slots of the snapshot, through the renderer `r`:
+///
+/// - `r.attr(name, value)` renders an attribute, and nothing for `null`, `undefined` or `false`.
+/// - `r.style(staticStyle, value, keys)` renders the `style` with a dynamic part, `keys` are the
+///   properties of the values array of the extracted styles, the holes of `keys` are skipped.
+/// - `r.spread(value, excludedKeys)` renders the attributes of a spread.
+/// - `r.slot(ctx, index)` renders the children of the `index`-th slot.
+///
+/// The branches of a switch are rendered as a conditional expression on its value.
+///
+/// The events, refs and gestures have no markup and are skipped.
+pub struct SnapshotRenderer<'a> {
+  registry: &'a ElementRegistry,
+  static_attrs: HashMap<i32, StaticAttrs>,
+  attr_parts: Vec<(usize, &'a DynamicPart)>,
+  slot_parts: Vec<(usize, &'a DynamicPart)>,
+  output: Vec<Expr>,
+  text: String,
+}
+
+impl<'a> SnapshotRenderer<'a> {
+  pub fn new(
+    registry: &'a ElementRegistry,
+    creator: &Function,
+    attr_parts: &'a [DynamicPart],
+    slot_parts: &'a [DynamicPart],
+  ) -> Self {
+    let mut renderer = SnapshotRenderer {
+      registry,
+      static_attrs: HashMap::new(),
+      attr_parts: attr_parts.iter().enumerate().collect(),
+      slot_parts: slot_parts.iter().enumerate().collect(),
+      output: vec![],
+      text: String::new(),
+    };
+    renderer.collect_static_attrs(creator);
+    renderer
+  }
+
+  fn collect_static_attrs(&mut self, creator: &Function) {
+    let stmts = match &creator.body {
+      Some(body) => &body.stmts,
+      None => return,
+    };
+
+    // The creator returns the elements in the order of their indices.
+    let element_indices = match stmts.last() {
+      Some(Stmt::Return(ReturnStmt { arg: Some(arg), .. })) => match &**arg {
+        Expr::Array(elements) => elements
+          .elems
+          .iter()
+          .enumerate()
+          .filter_map(
+            |(index, element)| match element.as_ref().map(|e| &*e.expr) {
+              Some(Expr::Ident(ident)) => Some((ident.to_id(), index as i32)),
+              _ => None,
+            },
+          )
+          .collect::<HashMap<_, _>>(),
+        _ => return,
+      },
+      _ => return,
+    };
+
+    for stmt in stmts {
+      let (element, callee, args) = match stmt {
+        // const el = __CreateMap(pageId, { provider: "amap" });
+        Stmt::Decl(Decl::Var(var)) => match (&var.decls[0].name, var.decls[0].init.as_deref()) {
+          (Pat::Ident(element), Some(Expr::Call(call))) => {
+            (element.to_id(), callee_name(call), &call.args[1..])
+          }
+          _ => continue,
+        },
+        // __SetAttribute(el, "name", "value");
+        Stmt::Expr(ExprStmt { expr, .. }) => match &**expr {
+          Expr::Call(call) => match call.args.first().map(|arg| &*arg.expr) {
+            Some(Expr::Ident(element)) => (element.to_id(), callee_name(call), &call.args[1..]),
+            _ => continue,
+          },
+          _ => continue,
+        },
+        _ => continue,
+      };
+      let Some(index) = element_indices.get(&element) else {
+        continue;
+      };
+      let static_attrs = self.static_attrs.entry(*index).or_default();
+
+      let lit_arg = |i: usize| match args.get(i).map(|arg| &*arg.expr) {
+        Some(Expr::Lit(lit)) => lit_to_string(lit),
+        _ => None,
+      };
+      match callee.as_deref() {
+        Some("__SetAttribute") | Some("__AddDataset") => {
+          if let (Some(name), Some(value)) = (lit_arg(0), lit_arg(1)) {
+            let name = if callee.as_deref() == Some("__AddDataset") {
+              format!("data-{}", name)
+            } else {
+              name
+            };
+            static_attrs.attrs.push((name, value));
+          }
+        }
+        Some("__SetClasses") => {
+          if let Some(value) = lit_arg(0) {
+            static_attrs.attrs.push(("class".into(), value));
+          }
+        }
+        Some("__SetID") => {
+          if let Some(value) = lit_arg(0) {
+            static_attrs.attrs.push(("id".into(), value));
+          }
+        }
+        Some("__SetInlineStyles") => {
+          if let Some(value) = lit_arg(0) {
+            static_attrs.style.push(value);
+          }
+        }
+        Some("__AddInlineStyle") => {
+          if let (Some(Expr::Lit(Lit::Num(key))), Some(value)) =
+            (args.first().map(|arg| &*arg.expr), lit_arg(1))
+          {
+            if let Some(name) = CSS_PROPERTY_NAMES.get(&(key.value as u32)) {
+              static_attrs.style.push(format!("{}:{}", name, value));
+            }
+          }
+        }
+        Some(creator)
+          if self
+            .registry
+            .elements
+            .values()
+            .any(|element| element.creator == creator) =>
+        {
+          // The creation attributes of the custom elements.
+          if let Some(Expr::Object(object)) = args.first().map(|arg| &*arg.expr) {
+            for prop in &object.props {
+              if let PropOrSpread::Prop(prop) = prop {
+                if let Prop::KeyValue(KeyValueProp {
+                  key: PropName::Str(name),
+                  value,
+                }) = &**prop
+                {
+                  if let Expr::Lit(value) = &**value {
+                    if let Some(value) = lit_to_string(value) {
+                      static_attrs.attrs.push((name.value.to_string(), value));
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// The renderer function of `elements`, which is the element tree of the snapshot.
+  pub fn render(mut self, elements: &[SnapshotElementManifest]) -> Expr {
+    for element in elements {
+      self.render_element(element);
+    }
+    self.flush_text();
+
+    let markup = concat(self.output);
+
+    quote!(
+      "function (ctx, r) { return $markup; }" as Expr,
+      markup: Expr = markup,
+    )
+  }
+
+  fn render_element(&mut self, element: &SnapshotElementManifest) {
+    if element.tag == "raw-text" {
+      self
+        .text
+        .push_str(&escape(element.text.as_deref().unwrap_or_default()));
+      return;
+    }
+
+    self.text.push('<');
+    self.text.push_str(&element.tag);
+
+    let StaticAttrs { attrs, style } = self.static_attrs.remove(&element.index).unwrap_or_default();
+    for (name, value) in attrs {
+      self.push_attr(&name, &value);
+    }
+    let style = style.join(";");
+
+    let mut has_dynamic_style = false;
+    let attr_parts = self.attr_parts.clone();
+    for (value_index, dynamic_part) in attr_parts {
+      let value = quote!(
+        "ctx.__values[$index]" as Expr,
+        index: Expr = i32_to_expr(&(value_index as i32)),
+      );
+      let expr = match dynamic_part {
+        DynamicPart::Attr(_, element_index, attr_name) if *element_index == element.index => {
+          match attr_name {
+            AttrName::Attr(_) | AttrName::Dataset(_) | AttrName::Class | AttrName::ID => {
+              renderer_call("attr", vec![str_expr(&attr_name.to_manifest_name()), value])
+            }
+            AttrName::TimingFlag => renderer_call(
+              "attr",
+              vec![
+                str_expr("__lynx_timing_flag"),
+                quote!("$value.__ltf" as Expr, value: Expr = value),
+              ],
+            ),
+            AttrName::Style => {
+              has_dynamic_style = true;
+              renderer_call("style", vec![str_expr(&style), value])
+            }
+            AttrName::ParsedStyle(block) => {
+              has_dynamic_style = true;
+              let keys = block
+                .iter()
+                // A property given by an unknown id is left as a hole, and skipped by `r.style`.
+                .map(|(key, _)| {
+                  CSS_PROPERTY_NAMES
+                    .get(key)
+                    .map(|name| str_expr(name).into())
+                })
+                .collect();
+              renderer_call(
+                "style",
+                vec![
+                  str_expr(&style),
+                  value,
+                  Expr::Array(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: keys,
+                  }),
+                ],
+              )
+            }
+            AttrName::Event(..)
+            | AttrName::WorkletEvent(..)
+            | AttrName::Ref
+            | AttrName::WorkletRef(_)
+            | AttrName::ListItemPlatformInfo
+            | AttrName::Gesture(_) => continue,
+          }
+        }
+        DynamicPart::Spread(_, element_index, excluded_keys) if *element_index == element.index => {
+          renderer_call(
+            "spread",
+            vec![
+              value,
+              Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: excluded_keys
+                  .iter()
+                  .map(|key| Some(str_expr(key).into()))
+                  .collect(),
+              }),
+            ],
+          )
+        }
+        _ => continue,
+      };
+      self.push_expr(expr);
+    }
+    if !has_dynamic_style && !style.is_empty() {
+      self.push_attr("style", &style);
+    }
+    self.text.push('>');
+
+    let switch =
+      self
+        .attr_parts
+        .iter()
+        .find_map(|(value_index, dynamic_part)| match dynamic_part {
+          DynamicPart::Switch(_, element_index, _) if *element_index == element.index => {
+            Some(*value_index)
+          }
+          _ => None,
+        });
+    match switch {
+      // The branches are the children of the wrapper, in the same order.
+      Some(value_index) => {
+        let mut branches = element
+          .children
+          .iter()
+          .map(|branch| self.render_to_expr(branch));
+        let consequent = branches.next().unwrap_or_else(|| str_expr(""));
+        let alternate = branches.next().unwrap_or_else(|| str_expr(""));
+        self.push_expr(Expr::Paren(ParenExpr {
+          span: DUMMY_SP,
+          expr: Box::new(quote!(
+            "ctx.__values[$index] ? $consequent : $alternate" as Expr,
+            index: Expr = i32_to_expr(&(value_index as i32)),
+            consequent: Expr = consequent,
+            alternate: Expr = alternate,
+          )),
+        }));
+      }
+      None => {
+        for child in &element.children {
+          self.render_element(child);
+        }
+      }
+    }
+
+    let slot_parts = self.slot_parts.clone();
+    for (slot_index, dynamic_part) in slot_parts {
+      match dynamic_part {
+        DynamicPart::Slot(_, element_index)
+        | DynamicPart::Children(_, element_index)
+        | DynamicPart::ListChildren(_, element_index)
+          if *element_index == element.index =>
+        {
+          self.push_expr(renderer_call(
+            "slot",
+            vec![quote!("ctx" as Expr), i32_to_expr(&(slot_index as i32))],
+          ));
+        }
+        _ => {}
+      }
+    }
+
+    self.text.push_str("</");
+    self.text.push_str(&element.tag);
+    self.text.push('>');
+  }
+
+  /// The markup of `element` as one expression, without touching the current output.
+  fn render_to_expr(&mut self, element: &SnapshotElementManifest) -> Expr {
+    self.flush_text();
+    let output = std::mem::take(&mut self.output);
+    self.render_element(element);
+    self.flush_text();
+    concat(std::mem::replace(&mut self.output, output))
+  }
+
+  fn push_attr(&mut self, name: &str, value: &str) {
+    self
+      .text
+      .push_str(&format!(" {}=\"{}\"", name, escape(value)));
+  }
+
+  fn push_expr(&mut self, expr: Expr) {
+    self.flush_text();
+    self.output.push(expr);
+  }
+
+  fn flush_text(&mut self) {
+    if !self.text.is_empty() {
+      let text = std::mem::take(&mut self.text);
+      self.output.push(str_expr(&text));
+    }
+  }
+}
+
+fn callee_name(call: &CallExpr) -> Option<String> {
+  match &call.callee {
+    Callee::Expr(callee) => match &**callee {
+      Expr::Ident(ident) => Some(ident.sym.to_string()),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+fn lit_to_string(lit: &Lit) -> Option<String> {
+  match lit {
+    Lit::Str(s) => Some(s.value.to_string()),
+    Lit::Num(n) => ConstValue::Num(n.value).to_js_string(),
+    Lit::Bool(b) => Some(b.value.to_string()),
+    _ => None,
+  }
+}
+
+fn concat(exprs: Vec<Expr>) -> Expr {
+  exprs
+    .into_iter()
+    .reduce(|left, right| {
+      Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op: BinaryOp::Add,
+        left: Box::new(left),
+        right: Box::new(right),
+      })
+    })
+    .unwrap_or_else(|| str_expr(""))
+}
+
+fn str_expr(s: &str) -> Expr {
+  Expr::Lit(Lit::Str(s.into()))
+}
+
+fn renderer_call(method: &str, args: Vec<Expr>) -> Expr {
+  Expr::Call(CallExpr {
+    span: DUMMY_SP,
+    ctxt: Default::default(),
+    callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+      span: DUMMY_SP,
+      obj: Box::new(quote!("r" as Expr)),
+      prop: MemberProp::Ident(IdentName::new(method.into(), DUMMY_SP)),
+    }))),
+    args: args.into_iter().map(Into::into).collect(),
+    type_args: None,
+  })
+}
+
+fn escape(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len());
+  for c in s.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use swc_core::ecma::{
+  ast::*,
+  atoms::Atom,
+  visit::{VisitMut, VisitMutWith},
+};
+
+/// Merge the snapshot definitions with the same uid in one scope into the first one, e.g.: the
+/// definitions of the modules concatenated by the bundler.
+///
+/// The modules wrapped in their own factory functions are not merged, a definition in one factory
+/// can not be referenced by the others.
+///
+/// The snapshots of different modules only share a uid when they are compiled with
+/// `JSXTransformerConfig::fingerprint_uid`.
+#[derive(Default)]
+pub struct SnapshotDedupeVisitor {
+  /// The number of the merged definitions.
+  pub deduped: usize,
+}
+
+/// The uid of `createSnapshot("__snapshot_xxx", ...)`, the callee may be transformed by the
+/// bundler, e.g.: `(0, _react.createSnapshot)`.
+fn snapshot_uid(expr: &Expr) -> Option<Atom> {
+  let Expr::Call(CallExpr {
+    callee: Callee::Expr(callee),
+    args,
+    ..
+  }) = expr
+  else {
+    return None;
+  };
+
+  let mut callee = &**callee;
+  loop {
+    callee = match callee {
+      Expr::Paren(ParenExpr { expr, .. }) => expr,
+      Expr::Seq(SeqExpr { exprs, .. }) => exprs.last()?,
+      _ => break,
+    };
+  }
+  let is_create_snapshot = match callee {
+    Expr::Ident(ident) => ident.sym == "createSnapshot",
+    Expr::Member(MemberExpr {
+      prop: MemberProp::Ident(prop),
+      ..
+    }) => prop.sym == "createSnapshot",
+    _ => false,
+  };
+  if !is_create_snapshot {
+    return None;
+  }
+
+  match args.first() {
+    Some(ExprOrSpread { spread: None, expr }) => match &**expr {
+      Expr::Lit(Lit::Str(Str { value, .. })) if value.starts_with("__snapshot_") => {
+        Some(value.clone())
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
+impl SnapshotDedupeVisitor {
+  fn dedupe_decl(&mut self, decl: &mut Decl, snapshots: &mut HashMap<Atom, Ident>) {
+    let Decl::Var(var) = decl else {
+      return;
+    };
+
+    for declarator in &mut var.decls {
+      let (Pat::Ident(BindingIdent { id, .. }), Some(init)) =
+        (&declarator.name, &mut declarator.init)
+      else {
+        continue;
+      };
+      let Some(uid) = snapshot_uid(init) else {
+        continue;
+      };
+
+      match snapshots.get(&uid) {
+        // The first definition is always evaluated before, since they are in the same statement list.
+        Some(first) => {
+          *init = Box::new(Expr::Ident(first.clone()));
+          self.deduped += 1;
+        }
+        None => {
+          snapshots.insert(uid, id.clone());
+        }
+      }
+    }
+  }
+}
+
+impl VisitMut for SnapshotDedupeVisitor {
+  fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+    n.visit_mut_children_with(self);
+
+    let mut snapshots = HashMap::new();
+    for stmt in n {
+      if let Stmt::Decl(decl) = stmt {
+        self.dedupe_decl(decl, &mut snapshots);
+      }
+    }
+  }
+
+  fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
+    n.visit_mut_children_with(self);
+
+    let mut snapshots = HashMap::new();
+    for item in n {
+      match item {
+        ModuleItem::Stmt(Stmt::Decl(decl))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+          self.dedupe_decl(decl, &mut snapshots);
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use swc_core::ecma::{
+    parser::{EsSyntax, Syntax},
+    transforms::testing::test,
+    visit::visit_mut_pass,
+  };
+
+  use crate::swc_plugin_snapshot_dedupe::SnapshotDedupeVisitor;
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax::default()),
+    |_| visit_mut_pass(SnapshotDedupeVisitor::default()),
+    should_dedupe_snapshots_in_same_scope,
+    r#"
+const __snapshot_6f4a5c2e1b3d7a90 = ReactLynx.createSnapshot("__snapshot_6f4a5c2e1b3d7a90", function() {
+  const pageId = ReactLynx.__pageId;
+  const el = __CreateView(pageId);
+  return [el];
+}, null, null, undefined, globDynamicComponentEntry);
+const __snapshot_6f4a5c2e1b3d7a901 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+  const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+  const el = __CreateView(pageId);
+  return [el];
+}, null, null, undefined, globDynamicComponentEntry);
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+  const pageId = ReactLynx.__pageId;
+  const el = __CreateText(pageId);
+  return [el];
+}, null, null, undefined, globDynamicComponentEntry);
+function lazy() {
+  // Another scope, which may be evaluated before the outer one.
+  const __snapshot_6f4a5c2e1b3d7a902 = ReactLynx.createSnapshot("__snapshot_6f4a5c2e1b3d7a90", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    return [el];
+  }, null, null, undefined, globDynamicComponentEntry);
+  const __snapshot_6f4a5c2e1b3d7a903 = ReactLynx.createSnapshot("__snapshot_6f4a5c2e1b3d7a90", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    return [el];
+  }, null, null, undefined, globDynamicComponentEntry);
+}
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Es(EsSyntax::default()),
+    |_| visit_mut_pass(SnapshotDedupeVisitor::default()),
+    should_not_dedupe_snapshots_across_module_factories,
+    r#"
+var __webpack_modules__ = {
+  "./a.jsx": function(module, __webpack_exports__, __webpack_require__) {
+    var _react__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("@lynx-js/react");
+    const __snapshot_6f4a5c2e1b3d7a90 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+      const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+      const el = __CreateView(pageId);
+      return [el];
+    }, null, null, undefined, globDynamicComponentEntry);
+  },
+  "./b.jsx": function(module, __webpack_exports__, __webpack_require__) {
+    var _react__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("@lynx-js/react");
+    // Not merged, it is in the scope of another module.
+    const __snapshot_6f4a5c2e1b3d7a90 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+      const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+      const el = __CreateView(pageId);
+      return [el];
+    }, null, null, undefined, globDynamicComponentEntry);
+  },
+  "./c.jsx + 1 modules": function(module, __webpack_exports__, __webpack_require__) {
+    var _react__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("@lynx-js/react");
+    // The concatenated modules share a scope, they are merged.
+    const __snapshot_6f4a5c2e1b3d7a90 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+      const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+      const el = __CreateView(pageId);
+      return [el];
+    }, null, null, undefined, globDynamicComponentEntry);
+    const __snapshot_6f4a5c2e1b3d7a901 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+      const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+      const el = __CreateView(pageId);
+      return [el];
+    }, null, null, undefined, globDynamicComponentEntry);
+  },
+};
+    "#
+  );
+}
@@ -0,0 +1,162 @@
+use swc_core::{
+  common::errors::HANDLER,
+  ecma::{
+    ast::*,
+    visit::{noop_visit_type, Visit, VisitWith},
+  },
+};
+
+use crate::diagnostics::{ids, lint};
+
+/// Report the asynchronous constructs of a main thread function which can not cross the threads.
+///
+/// These are diagnostics only, the function is transformed as it is. The worklet runtime does not
+/// resume a generator, and `runOnBackground(fn)()` returns a Promise instead of the result of `fn`:
+///
+/// ```js
+/// async function onTap() {
+///   'main thread';
+///   const data = await runOnBackground(fetchData)(); // ok
+///   const pending = runOnBackground(getSize)();      // ok, awaited later
+///   const size = runOnBackground(getSize)().width;   // a Promise has no `width`
+/// }
+/// ```
+pub fn check_async_worklet(function: &Function) {
+  if function.is_generator {
+    HANDLER.with(|handler| {
+      handler
+        .struct_span_warn_with_code(
+          function.span,
+          "A main thread function can not be a generator, the worklet runtime does not resume it",
+          lint(ids::GENERATOR_WORKLET),
+        )
+        .help("use an `async` function and `await` the values of the background thread instead")
+        .emit()
+    });
+  }
+
+  if let Some(body) = &function.body {
+    body.visit_with(&mut UnawaitedRunOnBackground {
+      is_async: function.is_async,
+    });
+  }
+}
+
+/// Find the Promises of `runOnBackground(fn)()` which are used as the result of `fn`, i.e.: the
+/// Promise is accessed, used as an operand, destructured or called.
+///
+/// A Promise which is stored or passed on may be awaited later, it is not reported.
+struct UnawaitedRunOnBackground {
+  /// Whether the innermost function is `async`.
+  is_async: bool,
+}
+
+impl UnawaitedRunOnBackground {
+  fn check_used_as_result(&self, expr: &Expr) {
+    let Some(call) = run_on_background_call(expr) else {
+      return;
+    };
+    HANDLER.with(|handler| {
+      handler
+        .struct_span_warn_with_code(
+          call.span,
+          "`runOnBackground(fn)()` returns a Promise of the result of `fn`, but the Promise is used as the result",
+          lint(ids::UNAWAITED_RUN_ON_BACKGROUND),
+        )
+        .help(if self.is_async {
+          "`await` the Promise"
+        } else {
+          "make the main thread function `async` and `await` the Promise"
+        })
+        .emit()
+    });
+  }
+}
+
+impl Visit for UnawaitedRunOnBackground {
+  noop_visit_type!();
+
+  fn visit_function(&mut self, n: &Function) {
+    let outer = self.is_async;
+    self.is_async = n.is_async;
+    n.visit_children_with(self);
+    self.is_async = outer;
+  }
+
+  fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+    let outer = self.is_async;
+    self.is_async = n.is_async;
+    n.visit_children_with(self);
+    self.is_async = outer;
+  }
+
+  fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+    // `const { width } = runOnBackground(fn)()`
+    if !n.name.is_ident() {
+      if let Some(init) = &n.init {
+        self.check_used_as_result(init);
+      }
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_expr(&mut self, n: &Expr) {
+    match n {
+      // `runOnBackground(fn)().width`, but not `runOnBackground(fn)().then()`
+      Expr::Member(MemberExpr { obj, prop, .. }) if !is_promise_method(prop) => {
+        self.check_used_as_result(obj)
+      }
+      // `runOnBackground(fn)() + 1`
+      Expr::Bin(BinExpr { left, right, .. }) => {
+        self.check_used_as_result(left);
+        self.check_used_as_result(right);
+      }
+      // `!runOnBackground(fn)()`, but not `void runOnBackground(fn)()`
+      Expr::Unary(UnaryExpr { op, arg, .. })
+        if !matches!(op, UnaryOp::Void | UnaryOp::TypeOf | UnaryOp::Delete) =>
+      {
+        self.check_used_as_result(arg)
+      }
+      // `${runOnBackground(fn)()}`
+      Expr::Tpl(Tpl { exprs, .. }) => exprs
+        .iter()
+        .for_each(|expr| self.check_used_as_result(expr)),
+      // `runOnBackground(fn)()()`
+      Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+      }) => self.check_used_as_result(callee),
+      _ => {}
+    }
+    n.visit_children_with(self);
+  }
+}
+
+/// `.then`, `.catch` or `.finally`.
+fn is_promise_method(prop: &MemberProp) -> bool {
+  match prop {
+    MemberProp::Ident(prop) => matches!(prop.sym.as_ref(), "then" | "catch" | "finally"),
+    _ => false,
+  }
+}
+
+/// `runOnBackground(fn)(...args)`
+fn run_on_background_call(expr: &Expr) -> Option<&CallExpr> {
+  let Expr::Call(call) = expr.unwrap_parens() else {
+    return None;
+  };
+  let Callee::Expr(callee) = &call.callee else {
+    return None;
+  };
+  let Expr::Call(CallExpr {
+    callee: Callee::Expr(inner),
+    ..
+  }) = callee.unwrap_parens()
+  else {
+    return None;
+  };
+  match &**inner {
+    Expr::Ident(ident) if ident.sym == "runOnBackground" => Some(call),
+    _ => None,
+  }
+}
@@ -68,6 +68,36 @@ impl ExtractingIdentsCollector {
     self.values_extracted.take()
   }
 
+  pub fn idents(&self) -> &[Ident] {
+    &self.idents_to_extract
+  }
+
+  /// The captured identifiers, with whether the whole value is captured instead of its members.
+  ///
+  /// The identifier of a whole value is at where it is captured as a whole.
+  pub fn captured(&self) -> Vec<(Ident, bool)> {
+    let mut captured: Vec<(Ident, bool)> = vec![];
+    for ident in &self.idents_to_extract {
+      if captured.iter().any(|(c, _)| c.to_id() == ident.to_id()) {
+        continue;
+      }
+      let whole = self.values_extracted.as_object().and_then(|values| {
+        values.props.iter().find_map(|prop| match prop {
+          PropOrSpread::Prop(prop) => match &**prop {
+            Prop::Shorthand(whole) if whole.to_id() == ident.to_id() => Some(whole.clone()),
+            _ => None,
+          },
+          _ => None,
+        })
+      });
+      captured.push(match whole {
+        Some(whole) => (whole, true),
+        None => (ident.clone(), false),
+      });
+    }
+    captured
+  }
+
   pub fn take_idents(&mut self) -> Vec<Ident> {
     self.idents_to_extract.take()
   }
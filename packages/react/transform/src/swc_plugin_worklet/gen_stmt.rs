@@ -1,11 +1,13 @@
 use crate::swc_plugin_worklet::extract_ident::ExtractingIdentsCollector;
 use crate::swc_plugin_worklet::worklet_type::WorkletType;
+use crate::swc_plugin_worklet::WorkletImports;
 use crate::target::TransformTarget;
 use crate::TransformMode;
 use std::collections::HashSet;
 use std::vec;
-use swc_core::common::DUMMY_SP;
+use swc_core::common::{Span, DUMMY_SP};
 use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{VisitMut, VisitMutWith};
 use swc_core::{quote, quote_expr};
 
 pub struct StmtGen {}
@@ -20,7 +22,7 @@ impl StmtGen {
     function: Box<Function>,
     ident_collector: &mut ExtractingIdentsCollector,
     is_class_member: bool,
-    named_imports: &mut HashSet<String>,
+    imports: &mut WorkletImports,
   ) -> (Box<Expr>, Stmt) {
     let hash = Expr::Lit(hash.into());
     let extracted_value = ident_collector.take_values();
@@ -29,7 +31,8 @@ impl StmtGen {
 
     (
       StmtGen::gen_transformed_worklet_expr(
-        if target == TransformTarget::MIXED {
+        // There is no worklet runtime when rendering on the server.
+        if matches!(target, TransformTarget::MIXED | TransformTarget::SSR) {
           TransformTarget::JS
         } else {
           target
@@ -38,14 +41,14 @@ impl StmtGen {
         ident_collector.take_this_expr(),
         extracted_js_fns.clone(),
         hash.clone(),
-        named_imports,
+        imports,
       ),
       StmtGen::gen_register_worklet_stmt(
         mode,
-        if target == TransformTarget::MIXED {
-          TransformTarget::LEPUS
-        } else {
-          target
+        match target {
+          TransformTarget::MIXED => TransformTarget::LEPUS,
+          TransformTarget::SSR => TransformTarget::JS,
+          _ => target,
         },
         worklet_type,
         function_name,
@@ -54,7 +57,7 @@ impl StmtGen {
         extracted_js_fns,
         hash,
         is_class_member,
-        named_imports,
+        imports,
       ),
     )
   }
@@ -68,10 +71,10 @@ impl StmtGen {
     extracted_this_expr: Box<Expr>,
     extracted_js_fns: Vec<(IdentName, Box<Expr>)>,
     hash: Expr,
-    named_imports: &mut HashSet<String>,
+    imports: &mut WorkletImports,
   ) -> Box<Expr> {
     if target == TransformTarget::JS && !extracted_js_fns.is_empty() {
-      named_imports.insert("transformToWorklet".into());
+      imports.runtime.insert("transformToWorklet".into());
     }
 
     let mut props: Vec<PropOrSpread> = vec![];
@@ -159,6 +162,8 @@ impl StmtGen {
 
   /*
    * registerWorklet($type, $hash, $function);
+   *
+   * The worklets of a custom kind are registered by its own function on the main thread.
    */
   fn gen_register_worklet_stmt(
     mode: TransformMode,
@@ -170,8 +175,9 @@ impl StmtGen {
     extracted_js_fns: Vec<(IdentName, Box<Expr>)>,
     hash: Expr,
     is_class_member: bool,
-    named_imports: &mut HashSet<String>,
+    imports: &mut WorkletImports,
   ) -> Stmt {
+    let span = function.span;
     let function_to_register = Box::new(StmtGen::gen_function_to_register(
       function,
       function_name,
@@ -181,9 +187,19 @@ impl StmtGen {
       is_class_member,
     ));
 
-    if target == TransformTarget::LEPUS {
-      named_imports.insert("loadWorkletRuntime".into());
-      quote!("loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal($type_, $hash, $fn_)" as Stmt,
+    let mut stmt = if target == TransformTarget::LEPUS {
+      imports.runtime.insert("loadWorkletRuntime".into());
+      let register = match &worklet_type {
+        WorkletType::Custom(custom) => {
+          imports
+            .custom
+            .insert((custom.import_source.clone(), custom.register.clone()));
+          Ident::from(custom.register.as_str())
+        }
+        _ => Ident::from("registerWorkletInternal"),
+      };
+      quote!("loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && $register($type_, $hash, $fn_)" as Stmt,
+        register = register,
         type_: Expr = Expr::Lit(worklet_type.type_str().into()).into(),
         hash: Expr = hash,
         fn_: Expr = Expr::Fn(FnExpr {
@@ -192,7 +208,7 @@ impl StmtGen {
             }).into(),
       )
     } else if mode == TransformMode::Development {
-      named_imports.insert("registerWorkletOnBackground".into());
+      imports.runtime.insert("registerWorkletOnBackground".into());
       quote!("registerWorkletOnBackground($type_, $hash, $fn_)" as Stmt,
         type_: Expr = Expr::Lit(worklet_type.type_str().into()).into(),
         hash: Expr = hash,
@@ -203,7 +219,10 @@ impl StmtGen {
       )
     } else {
       EmptyStmt { span: DUMMY_SP }.into()
-    }
+    };
+    // Map the generated code back to the worklet, the relocated body keeps its own spans.
+    stmt.visit_mut_with(&mut Respan(span));
+    stmt
   }
 
   fn gen_function_to_register(
@@ -246,12 +265,13 @@ impl StmtGen {
       ));
     }
 
-    stmts.append(&mut function.body.unwrap().stmts);
+    let body = function.body.unwrap();
+    stmts.extend(body.stmts);
 
     Function {
       body: BlockStmt {
         ctxt: Default::default(),
-        span: DUMMY_SP,
+        span: body.span,
         stmts,
       }
       .into(),
@@ -286,3 +306,14 @@ impl StmtGen {
     )
   }
 }
+
+/// Fill the dummy spans of the generated nodes with the span of the worklet.
+struct Respan(Span);
+
+impl VisitMut for Respan {
+  fn visit_mut_span(&mut self, span: &mut Span) {
+    if span.is_dummy() {
+      *span = self.0;
+    }
+  }
+}
@@ -1,4 +1,11 @@
 use sha1::{Digest, Sha1};
+use swc_core::ecma::{
+  ast::{Expr, FnExpr, Function},
+  codegen::to_code,
+  visit::VisitMutWith,
+};
+
+use crate::utils::{calc_hash_with_len, CanonicalIdents};
 
 pub struct WorkletHash {
   last_id: i32,
@@ -19,6 +26,20 @@ impl WorkletHash {
     )
   }
 
+  /// The hash of the worklet itself, i.e.: its normalized code and the layout of the captured
+  /// identifiers, which is the same across files and builds.
+  pub fn gen_content_addressed(function: &Function, captured_idents: &[String]) -> String {
+    let mut function = Expr::Fn(FnExpr {
+      ident: None,
+      function: Box::new(function.clone()),
+    });
+    function.visit_mut_with(&mut CanonicalIdents::default());
+    calc_hash_with_len(
+      &format!("{}\n{}", captured_idents.join(","), to_code(&function)),
+      16,
+    )
+  }
+
   fn calc_hash(s: &str) -> String {
     let mut hasher = Sha1::new();
     hasher.update(s.as_bytes());
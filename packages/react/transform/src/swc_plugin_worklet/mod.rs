@@ -1,19 +1,24 @@
+mod asynchrony;
 mod decl_collect;
 mod extract_ident;
 mod gen_stmt;
 mod globals;
 mod hash;
+mod serializability;
 mod worklet_type;
 
+use crate::swc_plugin_worklet::asynchrony::check_async_worklet;
 use crate::swc_plugin_worklet::extract_ident::{
   ExtractingIdentsCollector, ExtractingIdentsCollectorConfig,
 };
 use crate::swc_plugin_worklet::gen_stmt::StmtGen;
 use crate::swc_plugin_worklet::hash::WorkletHash;
+use crate::swc_plugin_worklet::serializability::UnserializableBindings;
 use crate::swc_plugin_worklet::worklet_type::WorkletType;
-use napi_derive::napi;
+use serde::Deserialize;
 use std::collections::HashSet;
 use std::vec;
+use swc_core::common::errors::HANDLER;
 use swc_core::common::util::take::Take;
 use swc_core::common::{Spanned, DUMMY_SP};
 use swc_core::ecma::ast::*;
@@ -21,10 +26,13 @@ use swc_core::ecma::utils::prepend_stmts;
 use swc_core::ecma::visit::VisitMutWith;
 use swc_core::ecma::visit::{noop_visit_mut_type, VisitMut};
 
+use crate::diagnostics::{ids, lint};
+use crate::panic_guard::record_span;
 use crate::target::TransformTarget;
 use crate::TransformMode;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 #[napi(object)]
 pub struct WorkletVisitorConfig {
   /// @public
@@ -37,9 +45,34 @@ pub struct WorkletVisitorConfig {
   /// @internal
   pub filename: String,
   /// @internal
-  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED'")]
+  #[napi(ts_type = "'LEPUS' | 'JS' | 'MIXED' | 'SSR'")]
   pub target: TransformTarget,
   pub runtime_pkg: String,
+  /// @public
+  /// Derive the hash of a worklet from its code and the identifiers it captures, instead of the
+  /// file and its position in the file. The hash is then stable across builds, and the identical
+  /// worklets of different files share one hash.
+  pub content_addressed_hash: Option<bool>,
+  /// @public
+  /// The directives of the custom worklet kinds, in addition to `"main thread"` and
+  /// `"use worklet"`. On the main thread, the worklets of a custom kind are registered by its own
+  /// function instead of `registerWorkletInternal`.
+  pub directives: Option<Vec<WorkletDirective>>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkletDirective {
+  /// The directive of the worklets, e.g.: `"use animation"`.
+  pub directive: String,
+  /// The type of the worklets, which is passed to the registration function and recorded in the
+  /// manifest, e.g.: `"animation"`.
+  pub type_str: String,
+  /// The function to register the worklets with, e.g.: `registerAnimationWorklet`.
+  pub register: String,
+  /// The module to import the registration function from.
+  pub import_source: String,
 }
 
 impl Default for WorkletVisitorConfig {
@@ -49,17 +82,32 @@ impl Default for WorkletVisitorConfig {
       target: TransformTarget::LEPUS,
       custom_global_ident_names: None,
       runtime_pkg: "NoDiff".into(),
+      content_addressed_hash: None,
+      directives: None,
     }
   }
 }
 
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct WorkletManifest {
+  pub hash: String,
+  /// The `typeStr` of a custom worklet kind, or one of the built-in types.
+  #[napi(ts_type = "'main-thread' | 'ui' | (string & {})")]
+  pub worklet_type: String,
+  /// The identifiers captured from the outer scope, which will be passed with `_c`.
+  pub captured_idents: Vec<String>,
+}
+
 pub struct WorkletVisitor {
   mode: TransformMode,
   content_hash: String,
   cfg: WorkletVisitorConfig,
   stmts_to_insert_at_top_level: Vec<Stmt>,
-  named_imports: HashSet<String>,
+  imports: WorkletImports,
   hasher: WorkletHash,
+  unserializable_bindings: UnserializableBindings,
+  pub manifest: Vec<WorkletManifest>,
 }
 
 impl Default for WorkletVisitor {
@@ -85,12 +133,17 @@ impl VisitMut for WorkletVisitor {
       return;
     }
 
+    record_span(n.span());
     let mut collector = ExtractingIdentsCollector::new(ExtractingIdentsCollectorConfig {
       custom_global_ident_names: self.cfg.custom_global_ident_names.clone(),
     });
     n.visit_mut_with(&mut collector);
 
-    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    let hash = self.gen_hash(
+      worklet_type.as_ref().unwrap(),
+      &n.as_method().unwrap().function,
+      &collector,
+    );
     let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
       self.mode,
       worklet_type.unwrap(),
@@ -106,7 +159,7 @@ impl VisitMut for WorkletVisitor {
       n.as_method().unwrap().function.clone(),
       &mut collector,
       true,
-      &mut self.named_imports,
+      &mut self.imports,
     );
 
     *n = ClassProp {
@@ -144,12 +197,17 @@ impl VisitMut for WorkletVisitor {
       return;
     }
 
+    record_span(n.span());
     let mut collector = ExtractingIdentsCollector::new(ExtractingIdentsCollectorConfig {
       custom_global_ident_names: self.cfg.custom_global_ident_names.clone(),
     });
     n.visit_mut_with(&mut collector);
 
-    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    let hash = self.gen_hash(
+      worklet_type.as_ref().unwrap(),
+      &n.as_fn_decl().unwrap().function,
+      &collector,
+    );
     let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
       self.mode,
       worklet_type.unwrap(),
@@ -159,7 +217,7 @@ impl VisitMut for WorkletVisitor {
       n.as_fn_decl().unwrap().function.clone(),
       &mut collector,
       false,
-      &mut self.named_imports,
+      &mut self.imports,
     );
 
     *n = VarDecl {
@@ -190,45 +248,47 @@ impl VisitMut for WorkletVisitor {
           return;
         }
 
+        record_span(n.span());
         let mut collector = ExtractingIdentsCollector::new(ExtractingIdentsCollectorConfig {
           custom_global_ident_names: self.cfg.custom_global_ident_names.clone(),
         });
         n.visit_mut_with(&mut collector);
 
-        let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+        let function = Box::new(Function {
+          ctxt: n.as_mut_arrow().unwrap().ctxt,
+          body: n
+            .as_mut_arrow()
+            .unwrap()
+            .body
+            .as_block_stmt()
+            .unwrap()
+            .clone()
+            .into(),
+          span: n.as_mut_arrow().unwrap().span,
+          return_type: n.as_mut_arrow().unwrap().return_type.clone(),
+          is_async: n.as_mut_arrow().unwrap().is_async,
+          is_generator: n.as_mut_arrow().unwrap().is_generator,
+          type_params: n.as_mut_arrow().unwrap().type_params.clone(),
+          decorators: vec![],
+          params: n
+            .as_mut_arrow()
+            .unwrap()
+            .params
+            .iter()
+            .map(|p| p.clone().into())
+            .collect(),
+        });
+        let hash = self.gen_hash(worklet_type.as_ref().unwrap(), &function, &collector);
         let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
           self.mode,
           worklet_type.unwrap(),
           hash,
           self.cfg.target,
           Ident::dummy(),
-          Box::new(Function {
-            ctxt: n.as_mut_arrow().unwrap().ctxt,
-            body: n
-              .as_mut_arrow()
-              .unwrap()
-              .body
-              .as_block_stmt()
-              .unwrap()
-              .clone()
-              .into(),
-            span: n.as_mut_arrow().unwrap().span,
-            return_type: n.as_mut_arrow().unwrap().return_type.clone(),
-            is_async: n.as_mut_arrow().unwrap().is_async,
-            is_generator: n.as_mut_arrow().unwrap().is_generator,
-            type_params: n.as_mut_arrow().unwrap().type_params.clone(),
-            decorators: vec![],
-            params: n
-              .as_mut_arrow()
-              .unwrap()
-              .params
-              .iter()
-              .map(|p| p.clone().into())
-              .collect(),
-          }),
+          function,
           &mut collector,
           false,
-          &mut self.named_imports,
+          &mut self.imports,
         );
 
         *n = *worklet_object_expr;
@@ -244,12 +304,17 @@ impl VisitMut for WorkletVisitor {
           return;
         }
 
+        record_span(n.span());
         let mut collector = ExtractingIdentsCollector::new(ExtractingIdentsCollectorConfig {
           custom_global_ident_names: self.cfg.custom_global_ident_names.clone(),
         });
         n.visit_mut_with(&mut collector);
 
-        let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+        let hash = self.gen_hash(
+          worklet_type.as_ref().unwrap(),
+          &n.as_fn_expr().unwrap().function,
+          &collector,
+        );
         let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
           self.mode,
           worklet_type.unwrap(),
@@ -259,7 +324,7 @@ impl VisitMut for WorkletVisitor {
           n.as_mut_fn_expr().unwrap().function.take(),
           &mut collector,
           false,
-          &mut self.named_imports,
+          &mut self.imports,
         );
 
         *n = *worklet_object_expr;
@@ -310,6 +375,7 @@ impl VisitMut for WorkletVisitor {
       return;
     }
 
+    record_span(n.span());
     let mut collector = ExtractingIdentsCollector::new(ExtractingIdentsCollectorConfig {
       custom_global_ident_names: self.cfg.custom_global_ident_names.clone(),
     });
@@ -320,7 +386,16 @@ impl VisitMut for WorkletVisitor {
       .unwrap()
       .visit_mut_with(&mut collector);
 
-    let hash = self.hasher.gen(&self.cfg.filename, &self.content_hash);
+    let hash = self.gen_hash(
+      worklet_type.as_ref().unwrap(),
+      &n.as_export_default_decl()
+        .unwrap()
+        .decl
+        .as_fn_expr()
+        .unwrap()
+        .function,
+      &collector,
+    );
     let (worklet_object_expr, register_worklet_stmt) = StmtGen::transform_worklet(
       self.mode,
       worklet_type.unwrap(),
@@ -336,7 +411,7 @@ impl VisitMut for WorkletVisitor {
         .take(),
       &mut collector,
       false,
-      &mut self.named_imports,
+      &mut self.imports,
     );
 
     *n = ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
@@ -360,82 +435,108 @@ impl VisitMut for WorkletVisitor {
   }
 
   fn visit_mut_module(&mut self, n: &mut Module) {
+    self.unserializable_bindings =
+      UnserializableBindings::collect(n, self.cfg.directives.clone().unwrap_or_default());
     n.visit_mut_children_with(self);
 
-    let mut specifiers = self.named_imports.iter().collect::<Vec<_>>();
+    let imports = self.imports.gen_module_items(&self.cfg.runtime_pkg);
+    prepend_stmts(&mut n.body, imports.into_iter());
+  }
+}
+
+/// The functions imported by the generated code.
+#[derive(Default)]
+pub struct WorkletImports {
+  /// The functions of `runtime_pkg`, e.g.: `registerWorkletInternal`.
+  pub runtime: HashSet<String>,
+  /// The registration functions of the custom worklet kinds, with their import sources.
+  pub custom: HashSet<(String, String)>,
+}
+
+impl WorkletImports {
+  fn gen_module_items(&self, runtime_pkg: &str) -> Vec<ModuleItem> {
+    let mut specifiers = self.runtime.iter().collect::<Vec<_>>();
+    // Sort to keep the output consistent
+    specifiers.sort();
+    let mut imports = gen_named_imports(runtime_pkg, specifiers);
+
+    let mut custom = self.custom.iter().collect::<Vec<_>>();
+    custom.sort();
+    for (src, imported) in custom {
+      imports.extend(gen_named_imports(src, vec![imported]));
+    }
+    imports
+  }
+}
 
-    if !specifiers.is_empty() {
-      // Sort to keep the output consistent
-      specifiers.sort();
+/// `import { x as __x } from "src"; var x = __x;`
+fn gen_named_imports(src: &str, specifiers: Vec<&String>) -> Vec<ModuleItem> {
+  if specifiers.is_empty() {
+    return vec![];
+  }
 
-      prepend_stmts(
-        &mut n.body,
-        vec![
-          ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+  vec![
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+      span: DUMMY_SP,
+      phase: ImportPhase::Evaluation,
+      specifiers: specifiers
+        .iter()
+        .map(|imported| {
+          ImportSpecifier::Named(ImportNamedSpecifier {
             span: DUMMY_SP,
-            phase: ImportPhase::Evaluation,
-            specifiers: specifiers
-              .iter()
-              .map(|imported| {
-                ImportSpecifier::Named(ImportNamedSpecifier {
-                  span: DUMMY_SP,
-                  is_type_only: false,
-                  local: Ident {
-                    ctxt: Default::default(),
-                    span: DUMMY_SP,
-                    sym: format!("__{imported}").into(),
-                    optional: false,
-                  },
-                  imported: Some(ModuleExportName::Ident(Ident {
-                    ctxt: Default::default(),
-                    span: DUMMY_SP,
-                    sym: imported.as_str().into(),
-                    optional: false,
-                  })),
-                })
-              })
-              .collect::<Vec<_>>(),
-            src: Box::new(Str {
+            is_type_only: false,
+            local: Ident {
+              ctxt: Default::default(),
+              span: DUMMY_SP,
+              sym: format!("__{imported}").into(),
+              optional: false,
+            },
+            imported: Some(ModuleExportName::Ident(Ident {
+              ctxt: Default::default(),
+              span: DUMMY_SP,
+              sym: imported.as_str().into(),
+              optional: false,
+            })),
+          })
+        })
+        .collect::<Vec<_>>(),
+      src: Box::new(Str {
+        span: DUMMY_SP,
+        raw: None,
+        value: src.into(),
+      }),
+      type_only: Default::default(),
+      with: Default::default(),
+    })),
+    ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+      ctxt: Default::default(),
+      span: DUMMY_SP,
+      kind: VarDeclKind::Var,
+      declare: false,
+      decls: specifiers
+        .into_iter()
+        .map(|name| VarDeclarator {
+          span: DUMMY_SP,
+          name: Pat::Ident(
+            Ident {
+              ctxt: Default::default(),
               span: DUMMY_SP,
-              raw: None,
-              value: self.cfg.runtime_pkg.clone().into(),
-            }),
-            type_only: Default::default(),
-            with: Default::default(),
-          })),
-          ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+              sym: name.as_str().into(),
+              optional: false,
+            }
+            .into(),
+          ),
+          init: Some(Box::new(Expr::Ident(Ident {
             ctxt: Default::default(),
             span: DUMMY_SP,
-            kind: VarDeclKind::Var,
-            declare: false,
-            decls: specifiers
-              .into_iter()
-              .map(|name| VarDeclarator {
-                span: DUMMY_SP,
-                name: Pat::Ident(
-                  Ident {
-                    ctxt: Default::default(),
-                    span: DUMMY_SP,
-                    sym: name.as_str().into(),
-                    optional: false,
-                  }
-                  .into(),
-                ),
-                init: Some(Box::new(Expr::Ident(Ident {
-                  ctxt: Default::default(),
-                  span: DUMMY_SP,
-                  sym: format!("__{name}").into(),
-                  optional: false,
-                }))),
-                definite: false,
-              })
-              .collect(),
-          })))),
-        ]
-        .into_iter(),
-      );
-    }
-  }
+            sym: format!("__{name}").into(),
+            optional: false,
+          }))),
+          definite: false,
+        })
+        .collect(),
+    })))),
+  ]
 }
 
 impl WorkletVisitor {
@@ -451,8 +552,52 @@ impl WorkletVisitor {
       cfg,
       stmts_to_insert_at_top_level: vec![],
       hasher: WorkletHash::new(),
-      named_imports: HashSet::default(),
+      unserializable_bindings: UnserializableBindings::default(),
+      imports: WorkletImports::default(),
+      manifest: vec![],
+    }
+  }
+
+  /// Generate the hash of a worklet and record it in the manifest.
+  ///
+  /// The captured values and the asynchronous constructs are checked here too, on the main thread
+  /// only, so that each of them is reported once.
+  fn gen_hash(
+    &mut self,
+    worklet_type: &WorkletType,
+    function: &Function,
+    collector: &ExtractingIdentsCollector,
+  ) -> String {
+    let mut captured_idents: Vec<String> = vec![];
+    for ident in collector.idents() {
+      if !captured_idents
+        .iter()
+        .any(|captured| *captured == *ident.sym)
+      {
+        captured_idents.push(ident.sym.to_string());
+      }
+    }
+
+    if matches!(
+      self.cfg.target,
+      TransformTarget::LEPUS | TransformTarget::MIXED
+    ) {
+      self.unserializable_bindings.check(&collector.captured());
+      check_async_worklet(function);
     }
+
+    let hash = if self.cfg.content_addressed_hash.unwrap_or(false) {
+      WorkletHash::gen_content_addressed(function, &captured_idents)
+    } else {
+      self.hasher.gen(&self.cfg.filename, &self.content_hash)
+    };
+    self.manifest.push(WorkletManifest {
+      hash: hash.clone(),
+      worklet_type: worklet_type.type_str().into(),
+      captured_idents,
+    });
+
+    hash
   }
 
   fn check_is_worklet_block(&self, n: &mut BlockStmt) -> Option<WorkletType> {
@@ -460,7 +605,30 @@ impl WorkletVisitor {
     if !stmts.is_empty() {
       match &mut stmts[0] {
         Stmt::Expr(ExprStmt { expr, span: _ }) => match &mut **expr {
-          Expr::Lit(Lit::Str(str)) => WorkletType::from_directive(str.value.to_string()),
+          Expr::Lit(Lit::Str(str)) => match WorkletType::from_directive(
+            str.value.to_string(),
+            self.cfg.directives.as_deref().unwrap_or_default(),
+          ) {
+            // The registration function is referenced by its name in the generated code.
+            Some(WorkletType::Custom(custom))
+              if Ident::verify_symbol(&custom.register).is_err() =>
+            {
+              HANDLER.with(|handler| {
+                handler
+                  .struct_span_err_with_code(
+                    str.span,
+                    &format!(
+                      "The `register` of the worklet directive \"{}\" is not a valid identifier: `{}`",
+                      custom.directive, custom.register
+                    ),
+                    lint(ids::INVALID_WORKLET_DIRECTIVE),
+                  )
+                  .emit()
+              });
+              None
+            }
+            worklet_type => worklet_type,
+          },
           _ => None,
         },
         _ => None,
@@ -473,7 +641,9 @@ impl WorkletVisitor {
 
 #[cfg(test)]
 mod tests {
-  use crate::swc_plugin_worklet::{TransformTarget, WorkletVisitor, WorkletVisitorConfig};
+  use crate::swc_plugin_worklet::{
+    TransformTarget, WorkletDirective, WorkletVisitor, WorkletVisitorConfig,
+  };
   use crate::TransformMode;
   use swc_core::common::Mark;
   use swc_core::ecma::parser::TsSyntax;
@@ -499,6 +669,8 @@ mod tests {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -528,6 +700,8 @@ function worklet(event: Event) {
           target: TransformTarget::MIXED,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -557,6 +731,8 @@ function worklet(event: Event) {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -586,6 +762,8 @@ function worklet(event: Event) {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -625,6 +803,8 @@ function X(event) {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -664,6 +844,8 @@ function X(event) {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -695,6 +877,8 @@ function Y(event) {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -728,6 +912,8 @@ function App() {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -755,6 +941,8 @@ let X = (event) => {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -782,6 +970,8 @@ let X = function (event) {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -813,6 +1003,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -844,6 +1036,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -874,6 +1068,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -904,6 +1100,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -935,6 +1133,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -966,6 +1166,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -996,6 +1198,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1026,6 +1230,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1058,6 +1264,8 @@ function X() {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1095,6 +1303,8 @@ function A() {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1131,6 +1341,8 @@ class Bpp extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1161,6 +1373,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1189,6 +1403,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1219,6 +1435,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1249,6 +1467,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: Some(vec!["myCustomGlobal".to_string()]),
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1280,6 +1500,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1312,6 +1534,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1341,6 +1565,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1370,6 +1596,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1410,6 +1638,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1450,6 +1680,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1479,6 +1711,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1507,6 +1741,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1535,6 +1771,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1587,6 +1825,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1614,6 +1854,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1641,6 +1883,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1679,6 +1923,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1720,6 +1966,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1761,6 +2009,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1804,6 +2054,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1847,6 +2099,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -1953,6 +2207,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -2059,6 +2315,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -2089,6 +2347,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -2118,6 +2378,8 @@ class App extends Component {
           target: TransformTarget::LEPUS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -2147,6 +2409,8 @@ class App extends Component {
           target: TransformTarget::JS,
           custom_global_ident_names: None,
           runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: None,
         }
       )),
       hygiene()
@@ -2161,4 +2425,79 @@ class App extends Component {
       }
     "#
   );
+
+  test!(
+    module,
+    Syntax::Typescript(TsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(WorkletVisitor::new(
+        TransformMode::Test,
+        WorkletVisitorConfig {
+          filename: "index.ts".into(),
+          target: TransformTarget::LEPUS,
+          custom_global_ident_names: None,
+          runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: Some(true),
+          directives: None,
+        }
+      )),
+      hygiene()
+    ),
+    should_use_content_addressed_hash,
+    r#"
+      const onTapA = (e) => {
+        'main thread';
+        e.currentTarget.setStyleProperty('color', color);
+      };
+      const onTapB = (e) => {
+        'main thread';
+        e.currentTarget.setStyleProperty('color', color);
+      };
+      function onTapC(event) {
+        'main thread';
+        event.currentTarget.setStyleProperty('color', color);
+      }
+    "#
+  );
+
+  test!(
+    module,
+    Syntax::Typescript(TsSyntax {
+      ..Default::default()
+    }),
+    |_| (
+      resolver(Mark::new(), Mark::new(), true),
+      visit_mut_pass(WorkletVisitor::new(
+        TransformMode::Test,
+        WorkletVisitorConfig {
+          filename: "index.ts".into(),
+          target: TransformTarget::LEPUS,
+          custom_global_ident_names: None,
+          runtime_pkg: "@lynx-js/react".into(),
+          content_addressed_hash: None,
+          directives: Some(vec![WorkletDirective {
+            directive: "use animation".into(),
+            type_str: "animation".into(),
+            register: "registerAnimationWorklet".into(),
+            import_source: "@lynx-js/animation".into(),
+          }]),
+        }
+      )),
+      hygiene()
+    ),
+    should_transform_custom_worklet_directive,
+    r#"
+      const onFrame = (progress) => {
+        'use animation';
+        ref.current.setStyleProperty('opacity', progress);
+      };
+      const onTap = (e) => {
+        'main thread';
+        e.currentTarget.setStyleProperty('color', color);
+      };
+    "#
+  );
 }
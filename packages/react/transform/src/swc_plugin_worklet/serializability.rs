@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use swc_core::{
+  common::{errors::HANDLER, Span},
+  ecma::{
+    ast::*,
+    visit::{noop_visit_type, Visit, VisitWith},
+  },
+};
+
+use crate::{
+  diagnostics::{ids, lint},
+  swc_plugin_worklet::{worklet_type::WorkletType, WorkletDirective},
+};
+
+/// The constructors whose instances are serialized as plain values.
+static SERIALIZABLE_CONSTRUCTORS: &[&str] = &["Array", "Object", "String", "Number", "Boolean"];
+
+/// Why the value of a binding can not cross the threads.
+#[derive(Debug, Clone, PartialEq)]
+enum Unserializable {
+  Class,
+  Function,
+  ClassInstance(String),
+  ReactRef,
+  Promise,
+}
+
+impl Unserializable {
+  fn describe(&self) -> String {
+    match self {
+      Unserializable::Class => "a class".into(),
+      Unserializable::Function => "a background function".into(),
+      Unserializable::ClassInstance(class) => format!("an instance of `{}`", class),
+      Unserializable::ReactRef => "a React ref object".into(),
+      Unserializable::Promise => "a Promise".into(),
+    }
+  }
+
+  fn help(&self) -> &'static str {
+    match self {
+      Unserializable::Function => "call it with `runOnBackground`",
+      Unserializable::ReactRef => "use `useMainThreadRef` for the refs of the main thread",
+      Unserializable::Promise => "capture the resolved value instead",
+      Unserializable::Class | Unserializable::ClassInstance(_) => {
+        "capture the plain data of the instance instead"
+      }
+    }
+  }
+
+  /// Whether capturing a member of the value is broken too, e.g.: `promise.then`.
+  fn is_member_unserializable(&self) -> bool {
+    matches!(self, Unserializable::Function | Unserializable::Promise)
+  }
+}
+
+/// The bindings of a module which are clearly not serializable, by their declarations.
+///
+/// ```js
+/// class Store {}
+/// const store = new Store();       // an instance of `Store`
+/// const ref = useRef(null);        // a React ref object
+/// const [count, setCount] = useState(0); // `setCount` is a background function
+/// ```
+#[derive(Default)]
+pub struct UnserializableBindings {
+  bindings: HashMap<Id, (Span, Unserializable)>,
+  directives: Vec<WorkletDirective>,
+}
+
+impl UnserializableBindings {
+  pub fn collect(module: &Module, directives: Vec<WorkletDirective>) -> Self {
+    let mut bindings = UnserializableBindings {
+      directives,
+      ..Default::default()
+    };
+    module.visit_with(&mut bindings);
+    bindings
+  }
+
+  /// Report the captured values of a worklet which are declared as unserializable.
+  ///
+  /// `captured` is the captured identifiers, with whether the whole value is captured instead of
+  /// its members.
+  pub fn check(&self, captured: &[(Ident, bool)]) {
+    for (ident, is_whole) in captured {
+      let Some((decl_span, unserializable)) = self.bindings.get(&ident.to_id()) else {
+        continue;
+      };
+      if !is_whole && !unserializable.is_member_unserializable() {
+        continue;
+      }
+
+      HANDLER.with(|handler| {
+        handler
+          .struct_span_warn_with_code(
+            ident.span,
+            &format!(
+              "`{}` is captured by a main thread function, but it is {} which can not be sent to the main thread",
+              ident.sym,
+              unserializable.describe()
+            ),
+            lint(ids::UNSERIALIZABLE_CAPTURE),
+          )
+          .span_note(*decl_span, &format!("`{}` is declared here", ident.sym))
+          .help(unserializable.help())
+          .emit()
+      });
+    }
+  }
+
+  fn insert(&mut self, ident: &Ident, unserializable: Unserializable) {
+    self
+      .bindings
+      .insert(ident.to_id(), (ident.span, unserializable));
+  }
+
+  fn is_worklet(&self, body: &Option<BlockStmt>) -> bool {
+    body
+      .as_ref()
+      .is_some_and(|block| self.is_worklet_block(block))
+  }
+
+  fn is_worklet_block(&self, block: &BlockStmt) -> bool {
+    match block.stmts.first() {
+      Some(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+        Expr::Lit(Lit::Str(directive)) => {
+          WorkletType::from_directive(directive.value.to_string(), &self.directives).is_some()
+        }
+        _ => false,
+      },
+      _ => false,
+    }
+  }
+
+  fn unserializable_init(&self, init: &Expr) -> Option<Unserializable> {
+    match init {
+      Expr::Arrow(ArrowExpr { body, .. }) => match &**body {
+        BlockStmtOrExpr::BlockStmt(block) if self.is_worklet_block(block) => None,
+        _ => Some(Unserializable::Function),
+      },
+      Expr::Fn(FnExpr { function, .. }) if !self.is_worklet(&function.body) => {
+        Some(Unserializable::Function)
+      }
+      _ => unserializable_init(init),
+    }
+  }
+}
+
+impl Visit for UnserializableBindings {
+  noop_visit_type!();
+
+  fn visit_class_decl(&mut self, n: &ClassDecl) {
+    self.insert(&n.ident, Unserializable::Class);
+    n.visit_children_with(self);
+  }
+
+  fn visit_fn_decl(&mut self, n: &FnDecl) {
+    if !self.is_worklet(&n.function.body) {
+      self.insert(&n.ident, Unserializable::Function);
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+    n.visit_children_with(self);
+
+    let Some(init) = &n.init else {
+      return;
+    };
+    let init = init.unwrap_parens();
+    match &n.name {
+      Pat::Ident(BindingIdent { id, .. }) => {
+        if let Some(unserializable) = self.unserializable_init(init) {
+          self.insert(id, unserializable);
+        }
+      }
+      // const [state, setState] = useState(0);
+      Pat::Array(ArrayPat { elems, .. }) => {
+        if let Expr::Call(call) = init {
+          if matches!(callee_name(call), Some("useState" | "useReducer")) {
+            if let Some(Some(Pat::Ident(BindingIdent { id, .. }))) = elems.get(1) {
+              self.insert(id, Unserializable::Function);
+            }
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+/// The unserializable values of the initializers other than functions.
+fn unserializable_init(init: &Expr) -> Option<Unserializable> {
+  match init {
+    Expr::Class(_) => Some(Unserializable::Class),
+    Expr::New(NewExpr { callee, .. }) => match callee.unwrap_parens() {
+      Expr::Ident(class) if class.sym == "Promise" => Some(Unserializable::Promise),
+      Expr::Ident(class) if SERIALIZABLE_CONSTRUCTORS.contains(&class.sym.as_ref()) => None,
+      Expr::Ident(class) => Some(Unserializable::ClassInstance(class.sym.to_string())),
+      _ => None,
+    },
+    Expr::Call(call) => match callee_name(call) {
+      Some("useRef" | "createRef") => Some(Unserializable::ReactRef),
+      Some("useCallback") => Some(Unserializable::Function),
+      Some("fetch") => Some(Unserializable::Promise),
+      _ => match &call.callee {
+        Callee::Expr(callee) => match callee.unwrap_parens() {
+          // Promise.resolve(), promise.then()
+          Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+          }) if matches!(obj.unwrap_parens(), Expr::Ident(obj) if obj.sym == "Promise")
+            || matches!(prop.sym.as_ref(), "then" | "catch" | "finally") =>
+          {
+            Some(Unserializable::Promise)
+          }
+          _ => None,
+        },
+        _ => None,
+      },
+    },
+    _ => None,
+  }
+}
+
+/// The name of `useRef()` and `React.useRef()`.
+fn callee_name(call: &CallExpr) -> Option<&str> {
+  match &call.callee {
+    Callee::Expr(callee) => match callee.unwrap_parens() {
+      Expr::Ident(ident) => Some(ident.sym.as_ref()),
+      Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+      }) if matches!(obj.unwrap_parens(), Expr::Ident(obj) if obj.sym == "React") => {
+        Some(prop.sym.as_ref())
+      }
+      _ => None,
+    },
+    _ => None,
+  }
+}
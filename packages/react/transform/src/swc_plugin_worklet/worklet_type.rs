@@ -1,17 +1,26 @@
+use crate::swc_plugin_worklet::WorkletDirective;
+
 #[derive(Clone)]
 pub enum WorkletType {
   Element,
   UI,
+  /// A worklet kind of `WorkletVisitorConfig.directives`.
+  Custom(WorkletDirective),
 }
 
 impl WorkletType {
-  pub fn from_directive(directive: String) -> Option<WorkletType> {
+  /// The built-in directives take precedence over the custom ones.
+  pub fn from_directive(directive: String, custom: &[WorkletDirective]) -> Option<WorkletType> {
     if directive == "main thread" {
       Some(WorkletType::Element)
     } else if directive == "use worklet" {
       Some(WorkletType::UI)
     } else {
-      None
+      custom
+        .iter()
+        .find(|custom| custom.directive == directive)
+        .cloned()
+        .map(WorkletType::Custom)
     }
   }
 
@@ -19,6 +28,7 @@ impl WorkletType {
     match self {
       WorkletType::Element => "main-thread",
       WorkletType::UI => "ui",
+      WorkletType::Custom(custom) => &custom.type_str,
     }
   }
 }
@@ -1,10 +1,15 @@
-#[derive(Debug, PartialEq, Clone, Copy)]
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
 pub enum TransformTarget {
   LEPUS,
   JS,
   MIXED,
+  /// Renders the snapshots to markup, e.g.: for the first screen of web previews.
+  SSR,
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::FromNapiValue for TransformTarget {
   unsafe fn from_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -22,6 +27,7 @@ impl napi::bindgen_prelude::FromNapiValue for TransformTarget {
       "LEPUS" => Ok(TransformTarget::LEPUS),
       "JS" => Ok(TransformTarget::JS),
       "MIXED" => Ok(TransformTarget::MIXED),
+      "SSR" => Ok(TransformTarget::SSR),
       _ => Err(napi::bindgen_prelude::error!(
         napi::bindgen_prelude::Status::InvalidArg,
         "value `{}` does not match any variant of enum `{}`",
@@ -32,6 +38,7 @@ impl napi::bindgen_prelude::FromNapiValue for TransformTarget {
   }
 }
 
+#[cfg(feature = "napi")]
 impl napi::bindgen_prelude::ToNapiValue for TransformTarget {
   unsafe fn to_napi_value(
     env: napi::bindgen_prelude::sys::napi_env,
@@ -41,6 +48,7 @@ impl napi::bindgen_prelude::ToNapiValue for TransformTarget {
       TransformTarget::LEPUS => "LEPUS",
       TransformTarget::JS => "JS",
       TransformTarget::MIXED => "MIXED",
+      TransformTarget::SSR => "SSR",
     };
     <&str>::to_napi_value(env, val)
   }
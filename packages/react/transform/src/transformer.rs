@@ -0,0 +1,233 @@
+use swc_core::{base::config::IsModule, common::Globals, ecma::parser::Syntax};
+
+use crate::{
+  transform_react_lynx_dual_inner, transform_react_lynx_inner, BoolOr, CSSScopeVisitorConfig,
+  CompatVisitorConfig, DefineDCEVisitorConfig, DirectiveDCEVisitorConfig,
+  DynamicImportVisitorConfig, InjectVisitorConfig, IsModuleConfig, JSXTransformerConfig,
+  RefreshVisitorConfig, ShakeVisitorConfig, SyntaxConfig, TransformMode, TransformNodiffDualOutput,
+  TransformNodiffOptions, TransformNodiffOutput, WorkletVisitorConfig,
+};
+
+/// The Rust entry of the ReactLynx transform.
+///
+/// It composes the same passes as `transformReactLynx`, e.g.: `JSXTransformer`, `WorkletVisitor`
+/// and `CSSScopeVisitor`. Each pass accepts either `bool` or its config, same as the JS API.
+///
+/// ```no_run
+/// use react_transform::{JSXTransformerConfig, TransformTarget, Transformer};
+///
+/// let output = Transformer::new("index.jsx")
+///   .snapshot(JSXTransformerConfig {
+///     target: TransformTarget::JS,
+///     ..Default::default()
+///   })
+///   .css_scope(false)
+///   .transform("export const App = () => <view />");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Transformer {
+  options: TransformNodiffOptions,
+}
+
+macro_rules! impl_from_config {
+  ($($config:ty),*) => {
+    $(
+      impl From<$config> for BoolOr<$config> {
+        fn from(value: $config) -> Self {
+          BoolOr::Value(value)
+        }
+      }
+    )*
+  };
+}
+
+impl_from_config!(
+  CSSScopeVisitorConfig,
+  JSXTransformerConfig,
+  ShakeVisitorConfig,
+  CompatVisitorConfig,
+  RefreshVisitorConfig,
+  DefineDCEVisitorConfig,
+  DirectiveDCEVisitorConfig,
+  WorkletVisitorConfig,
+  DynamicImportVisitorConfig,
+  InjectVisitorConfig
+);
+
+impl Transformer {
+  pub fn new(filename: impl Into<String>) -> Self {
+    Self {
+      options: TransformNodiffOptions {
+        filename: filename.into(),
+        ..Default::default()
+      },
+    }
+  }
+
+  pub fn mode(mut self, mode: TransformMode) -> Self {
+    self.options.mode = Some(mode);
+    self
+  }
+
+  pub fn syntax(mut self, syntax: Syntax) -> Self {
+    self.options.syntax_config = Some(SyntaxConfig(syntax));
+    self
+  }
+
+  pub fn is_module(mut self, is_module: IsModule) -> Self {
+    self.options.is_module = Some(IsModuleConfig(is_module));
+    self
+  }
+
+  /// Generate source map, a `String` is used as the output path of the source map.
+  pub fn sourcemap(mut self, sourcemap: BoolOr<String>) -> Self {
+    self.options.sourcemap = sourcemap;
+    self
+  }
+
+  /// The source map of the input code, see `TransformNodiffOptions::input_source_map`.
+  pub fn input_source_map(mut self, input_source_map: BoolOr<String>) -> Self {
+    self.options.input_source_map = Some(input_source_map);
+    self
+  }
+
+  pub fn css_scope(mut self, config: impl Into<BoolOr<CSSScopeVisitorConfig>>) -> Self {
+    self.options.css_scope = config.into();
+    self
+  }
+
+  pub fn snapshot(mut self, config: impl Into<BoolOr<JSXTransformerConfig>>) -> Self {
+    self.options.snapshot = Some(config.into());
+    self
+  }
+
+  pub fn shake(mut self, config: impl Into<BoolOr<ShakeVisitorConfig>>) -> Self {
+    self.options.shake = config.into();
+    self
+  }
+
+  pub fn compat(mut self, config: impl Into<BoolOr<CompatVisitorConfig>>) -> Self {
+    self.options.compat = config.into();
+    self
+  }
+
+  pub fn refresh(mut self, config: impl Into<BoolOr<RefreshVisitorConfig>>) -> Self {
+    self.options.refresh = config.into();
+    self
+  }
+
+  pub fn define_dce(mut self, config: impl Into<BoolOr<DefineDCEVisitorConfig>>) -> Self {
+    self.options.define_dce = config.into();
+    self
+  }
+
+  pub fn directive_dce(mut self, config: impl Into<BoolOr<DirectiveDCEVisitorConfig>>) -> Self {
+    self.options.directive_dce = config.into();
+    self
+  }
+
+  pub fn worklet(mut self, config: impl Into<BoolOr<WorkletVisitorConfig>>) -> Self {
+    self.options.worklet = config.into();
+    self
+  }
+
+  pub fn dynamic_import(mut self, config: impl Into<BoolOr<DynamicImportVisitorConfig>>) -> Self {
+    self.options.dynamic_import = Some(config.into());
+    self
+  }
+
+  pub fn inject(mut self, config: impl Into<BoolOr<InjectVisitorConfig>>) -> Self {
+    self.options.inject = Some(config.into());
+    self
+  }
+
+  pub fn options(&self) -> &TransformNodiffOptions {
+    &self.options
+  }
+
+  /// Transform `code` with a fresh swc [`Globals`].
+  pub fn transform(&self, code: impl Into<String>) -> TransformNodiffOutput {
+    self.transform_with_globals(&Globals::new(), code)
+  }
+
+  /// Transform `code` with the given swc [`Globals`], which can be reused by many modules.
+  pub fn transform_with_globals(
+    &self,
+    globals: &Globals,
+    code: impl Into<String>,
+  ) -> TransformNodiffOutput {
+    transform_react_lynx_inner(globals, code.into(), self.options.clone())
+  }
+
+  /// Transform `code` for both the main thread and the background thread, parsing it only once.
+  pub fn transform_dual(&self, code: impl Into<String>) -> TransformNodiffDualOutput {
+    self.transform_dual_with_globals(&Globals::new(), code)
+  }
+
+  /// Same as [`Transformer::transform_dual`] with the given swc [`Globals`].
+  pub fn transform_dual_with_globals(
+    &self,
+    globals: &Globals,
+    code: impl Into<String>,
+  ) -> TransformNodiffDualOutput {
+    transform_react_lynx_dual_inner(globals, code.into(), self.options.clone())
+  }
+}
+
+impl From<TransformNodiffOptions> for Transformer {
+  fn from(options: TransformNodiffOptions) -> Self {
+    Self { options }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    BoolOr, JSXTransformerConfig, TransformMode, TransformNodiffOptions, TransformTarget,
+    Transformer,
+  };
+
+  #[test]
+  fn test_transformer_builder() {
+    let transformer = Transformer::new("index.jsx")
+      .mode(TransformMode::Test)
+      .snapshot(JSXTransformerConfig {
+        target: TransformTarget::JS,
+        ..Default::default()
+      })
+      .css_scope(false);
+
+    let options = transformer.options();
+    assert_eq!(options.filename, "index.jsx");
+    assert!(matches!(options.css_scope, BoolOr::Bool(false)));
+    assert!(matches!(
+      &options.snapshot,
+      Some(BoolOr::Value(JSXTransformerConfig {
+        target: TransformTarget::JS,
+        ..
+      }))
+    ));
+
+    let output = transformer.transform("export const App = () => <view />");
+    assert!(output.errors.is_empty());
+    assert!(output.code.contains("createSnapshot"));
+
+    // Same output as the options based API.
+    let options = TransformNodiffOptions {
+      filename: "index.jsx".into(),
+      mode: Some(TransformMode::Test),
+      snapshot: Some(BoolOr::Value(JSXTransformerConfig {
+        target: TransformTarget::JS,
+        ..Default::default()
+      })),
+      css_scope: BoolOr::Bool(false),
+      ..Default::default()
+    };
+    assert_eq!(
+      Transformer::from(options)
+        .transform("export const App = () => <view />")
+        .code,
+      output.code
+    );
+  }
+}
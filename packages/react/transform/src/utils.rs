@@ -1,6 +1,13 @@
 use serde_json::{json, Value};
 use sha1::{Digest, Sha1};
-use swc_core::ecma::ast::*;
+use std::collections::HashMap;
+use swc_core::{
+  base::sourcemap,
+  common::SyntaxContext,
+  ecma::{ast::*, visit::VisitMut},
+};
+
+use crate::BoolOr;
 
 // https://github.com/swc-project/swc/blob/v1.5.8/crates/swc_ecma_transforms_optimization/src/json_parse.rs#L95
 pub fn jsonify(e: Expr) -> Value {
@@ -54,9 +61,64 @@ pub fn jsonify(e: Expr) -> Value {
 }
 
 pub fn calc_hash(s: &str) -> String {
+  calc_hash_with_len(s, 5)
+}
+
+/// Same as [`calc_hash`] with `len` hex digits, for the hashes that should not collide across
+/// modules.
+pub fn calc_hash_with_len(s: &str, len: usize) -> String {
   let mut hasher = Sha1::new();
   hasher.update(s.as_bytes());
   let sum = hasher.finalize();
 
-  hex::encode(sum)[0..5].to_string()
+  hex::encode(sum)[0..len].to_string()
+}
+
+/// Load the source map produced by the previous loaders.
+///
+/// - `BoolOr::Bool(true)` reads the inline `//# sourceMappingURL=data:...` of `code`.
+/// - `BoolOr::Value(map)` parses `map` as a JSON source map.
+///
+/// `Ok(None)` is returned if there is no input source map.
+pub fn get_input_source_map(
+  input_source_map: &BoolOr<String>,
+  code: &str,
+) -> Result<Option<sourcemap::SourceMap>, String> {
+  let decoded = match input_source_map {
+    BoolOr::Bool(false) => return Ok(None),
+    BoolOr::Bool(true) => match sourcemap::locate_sourcemap_reference_slice(code.as_bytes()) {
+      Ok(Some(reference)) if reference.get_url().starts_with("data:") => {
+        sourcemap::decode_data_url(reference.get_url())
+      }
+      // Only inline source map is supported, external files should be passed as JSON string.
+      Ok(_) => return Ok(None),
+      Err(err) => return Err(err.to_string()),
+    },
+    BoolOr::Value(map) => sourcemap::decode_slice(map.as_bytes()),
+  };
+
+  match decoded.map_err(|err| err.to_string())? {
+    sourcemap::DecodedMap::Regular(map) => Ok(Some(map)),
+    sourcemap::DecodedMap::Index(index) => index.flatten().map(Some).map_err(|err| err.to_string()),
+    sourcemap::DecodedMap::Hermes(_) => Err("Hermes source map is not supported".into()),
+  }
+}
+
+/// Rename the hygiene-marked identifiers by the order of their first appearance, so that the
+/// printed code only depends on the structure.
+#[derive(Default)]
+pub struct CanonicalIdents {
+  ids: HashMap<Id, usize>,
+}
+
+impl VisitMut for CanonicalIdents {
+  fn visit_mut_ident(&mut self, n: &mut Ident) {
+    if n.ctxt == SyntaxContext::empty() {
+      return;
+    }
+    let len = self.ids.len();
+    let index = *self.ids.entry(n.to_id()).or_insert(len);
+    n.sym = format!("{}${}", n.sym, index).into();
+    n.ctxt = SyntaxContext::empty();
+  }
 }
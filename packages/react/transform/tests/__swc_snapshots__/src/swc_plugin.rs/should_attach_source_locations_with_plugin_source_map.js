@@ -0,0 +1,28 @@
+const __snapshot_a99d6_App_0_6848737e = require('@lynx-js/react/internal').createSnapshot("__snapshot_a99d6_App_0_6848737e", function() {
+    const pageId = require('@lynx-js/react/internal').__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateText(pageId);
+    __AppendElement(el, el1);
+    const el2 = __CreateRawText("Hello");
+    __AppendElement(el1, el2);
+    __SetAttribute(el, "__lynx_source_location", {
+        file: "index.jsx",
+        line: 4,
+        column: 9,
+        component: "App"
+    });
+    __SetAttribute(el1, "__lynx_source_location", {
+        file: "index.jsx",
+        line: 5,
+        column: 11,
+        component: "App"
+    });
+    return [
+        el,
+        el1,
+        el2
+    ];
+}, null, null, undefined, globDynamicComponentEntry);
+function App() {
+    return <__snapshot_a99d6_App_0_6848737e/>;
+}
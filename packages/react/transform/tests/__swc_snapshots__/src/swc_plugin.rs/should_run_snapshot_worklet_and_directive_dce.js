@@ -0,0 +1,35 @@
+import * as ReactLynx from "@lynx-js/react";
+import { loadWorkletRuntime as __loadWorkletRuntime } from "NoDiff";
+var loadWorkletRuntime = __loadWorkletRuntime;
+import './index.css';
+const __snapshot_a99d6_test_1 = ReactLynx.createSnapshot("__snapshot_a99d6_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateText(pageId);
+    __AppendElement(el, el1);
+    const el2 = __CreateRawText("Hello");
+    __AppendElement(el1, el2);
+    return [
+        el,
+        el1,
+        el2
+    ];
+}, [
+    (snapshot, index, oldValue)=>ReactLynx.updateWorkletEvent(snapshot, index, oldValue, 0, "main-thread", "bindEvent", "tap"),
+    (snapshot, index, oldValue)=>ReactLynx.updateEvent(snapshot, index, oldValue, 0, "bindEvent", "tap", '')
+], null, undefined, globDynamicComponentEntry);
+function App() {
+    let onTap = {
+        _lepusWorkletHash: "a77b:test:1"
+    };
+    function onClick() {}
+    return <__snapshot_a99d6_test_1 values={[
+        onTap,
+        1
+    ]}/>;
+}
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "a77b:test:1", function(e) {
+    const onTap = lynxWorkletImpl._workletMap["a77b:test:1"].bind(this);
+    'main thread';
+    console.log(e);
+});
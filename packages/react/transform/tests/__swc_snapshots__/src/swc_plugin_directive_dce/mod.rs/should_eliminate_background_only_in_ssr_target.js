@@ -0,0 +1,7 @@
+function keepMe() {
+    function eliminateMe() {}
+    function keepMeToo() {
+        'use lepus only';
+        console.log("lepus only");
+    }
+}
@@ -1,5 +1,5 @@
 import { jsx as _jsx } from "@lynx-js/react/jsx-runtime";
-const __snapshot_da39a_test_1 = require('@lynx-js/react/internal').createSnapshot("__snapshot_da39a_test_1", function() {
+const __snapshot_da39a_Comp_0_5eb8de7f = require('@lynx-js/react/internal').createSnapshot("__snapshot_da39a_Comp_0_5eb8de7f", function() {
     const pageId = require('@lynx-js/react/internal').__pageId;
     const el = __CreateView(pageId);
     const el1 = __CreateText(pageId);
@@ -16,7 +16,7 @@ const __snapshot_da39a_test_1 = require('@lynx-js/react/internal').createSnapsho
 ], null, undefined, globDynamicComponentEntry);
 function Comp() {
     const handleTap = ()=>{};
-    return _jsx(__snapshot_da39a_test_1, {
+    return _jsx(__snapshot_da39a_Comp_0_5eb8de7f, {
         values: [
             handleTap
         ]
@@ -9,16 +9,6 @@ const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_
         el1
     ];
 }, null, null, undefined, globDynamicComponentEntry);
-const __snapshot_da39a_test_3 = ReactLynx.createSnapshot("__snapshot_da39a_test_3", function() {
-    const pageId = ReactLynx.__pageId;
-    const el = __CreateElement("list-item", pageId);
-    const el1 = __CreateRawText("!!!");
-    __AppendElement(el, el1);
-    return [
-        el,
-        el1
-    ];
-}, null, null, undefined, globDynamicComponentEntry);
 const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function(snapshotInstance) {
     const pageId = ReactLynx.__pageId;
     const el = ReactLynx.snapshotCreateList(pageId, snapshotInstance, 0);
@@ -32,14 +22,4 @@ const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_
     ]
 ], undefined, globDynamicComponentEntry);
 let a = __snapshot_da39a_test_1;
-const __snapshot_da39a_test_4 = ReactLynx.createSnapshot("__snapshot_da39a_test_4", function() {
-    const pageId = ReactLynx.__pageId;
-    const el = __CreateElement("list-item", pageId);
-    const el1 = __CreateRawText("!!!");
-    __AppendElement(el, el1);
-    return [
-        el,
-        el1
-    ];
-}, null, null, undefined, globDynamicComponentEntry);
-let b = __snapshot_da39a_test_4;
+let b = __snapshot_da39a_test_2;
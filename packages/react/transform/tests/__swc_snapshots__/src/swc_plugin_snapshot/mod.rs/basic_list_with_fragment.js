@@ -6,13 +6,6 @@ const __snapshot_da39a_test_3 = ReactLynx.createSnapshot("__snapshot_da39a_test_
         el
     ];
 }, null, null, undefined, globDynamicComponentEntry);
-const __snapshot_da39a_test_4 = ReactLynx.createSnapshot("__snapshot_da39a_test_4", function() {
-    const pageId = ReactLynx.__pageId;
-    const el = __CreateElement("list-item", pageId);
-    return [
-        el
-    ];
-}, null, null, undefined, globDynamicComponentEntry);
 const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_2", function(snapshotInstance) {
     const pageId = ReactLynx.__pageId;
     const el = ReactLynx.snapshotCreateList(pageId, snapshotInstance, 0);
@@ -56,5 +49,5 @@ const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_
 ], undefined, globDynamicComponentEntry);
 <__snapshot_da39a_test_1><__snapshot_da39a_test_2>{<>
           <__snapshot_da39a_test_3/>
-          <__snapshot_da39a_test_4/>
+          <__snapshot_da39a_test_3/>
         </>}</__snapshot_da39a_test_2><__snapshot_da39a_test_5><A/></__snapshot_da39a_test_5></__snapshot_da39a_test_1>;
@@ -4,6 +4,7 @@ const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_
     const pageId = ReactLynx.__pageId;
     const el = __CreateView(pageId);
     const el1 = __CreateText(pageId);
+    __SetAttribute(el1, "after", "aaa");
     __AppendElement(el, el1);
     const el2 = __CreateRawText("!!!");
     __AppendElement(el1, el2);
@@ -13,14 +14,15 @@ const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_
         el2
     ];
 }, [
-    (snapshot, index, oldValue)=>ReactLynx.updateSpread(snapshot, index, oldValue, 1)
+    (snapshot, index, oldValue)=>ReactLynx.updateSpread(snapshot, index, oldValue, 1, [
+            "after"
+        ])
 ], null, undefined, globDynamicComponentEntry);
 _jsx(__snapshot_da39a_test_1, {
     values: [
         {
             "before": "bbb",
             ...obj,
-            "after": "aaa",
             __spread: true
         }
     ]
@@ -1,20 +1,23 @@
-const __snapshot_da39a_test_1 = require('@lynx-js/react/internal').createSnapshot("__snapshot_da39a_test_1", function() {
+const __snapshot_da39a_0_13f11ae5 = require('@lynx-js/react/internal').createSnapshot("__snapshot_da39a_0_13f11ae5", function() {
     const pageId = require('@lynx-js/react/internal').__pageId;
     const el = __CreateView(pageId);
     return [
         el
     ];
 }, [
-    (snapshot, index, oldValue)=>require('@lynx-js/react/internal').updateSpread(snapshot, index, oldValue, 0)
+    (snapshot, index, oldValue)=>require('@lynx-js/react/internal').updateSpread(snapshot, index, oldValue, 0, [
+            "main-thread:bindtap"
+        ]),
+    (snapshot, index, oldValue)=>require('@lynx-js/react/internal').updateWorkletEvent(snapshot, index, oldValue, 0, "main-thread", "bindEvent", "tap")
 ], require('@lynx-js/react/internal').__DynamicPartChildren_0, undefined, globDynamicComponentEntry);
-<__snapshot_da39a_test_1 values={[
+<__snapshot_da39a_0_13f11ae5 values={[
     {
         ...{
             style: {
                 height: "100px"
             }
         },
-        "main-thread:bindtap": xxx,
         __spread: true
-    }
-]}>{[]}</__snapshot_da39a_test_1>;
+    },
+    xxx
+]}>{[]}</__snapshot_da39a_0_13f11ae5>;
@@ -1,5 +1,5 @@
 import * as ReactLynxRuntimeComponents from '@lynx-js/react/runtime-components';
-const __snapshot_da39a_test_1 = require('@lynx-js/react/internal').createSnapshot("__snapshot_da39a_test_1", function() {
+const __snapshot_da39a_0_e09d5246 = require('@lynx-js/react/internal').createSnapshot("__snapshot_da39a_0_e09d5246", function() {
     const pageId = require('@lynx-js/react/internal').__pageId;
     const el = __CreateView(pageId);
     return [
@@ -7,8 +7,8 @@ const __snapshot_da39a_test_1 = require('@lynx-js/react/internal').createSnapsho
     ];
 }, null, require('@lynx-js/react/internal').__DynamicPartChildren_0, undefined, globDynamicComponentEntry);
 <ReactLynxRuntimeComponents.Page custom-key-str="custom-value" custom-key-var={customVariable} class="classValue" data-attr={dataAttr}>
-      <__snapshot_da39a_test_1>{[
+      <__snapshot_da39a_0_e09d5246>{[
     <ReactLynxRuntimeComponents.Page/>,
     <A/>
-]}</__snapshot_da39a_test_1>
+]}</__snapshot_da39a_0_e09d5246>
     </ReactLynxRuntimeComponents.Page>;
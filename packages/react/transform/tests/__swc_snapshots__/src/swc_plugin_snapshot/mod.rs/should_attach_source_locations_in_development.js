@@ -0,0 +1,72 @@
+const __snapshot_c0e9e_App_items_0_00e76d20 = /*#__PURE__*/ require('@lynx-js/react/internal').createSnapshot("__snapshot_c0e9e_App_items_0_00e76d20", function() {
+    const pageId = require('@lynx-js/react/internal').__pageId;
+    const el = __CreateText(pageId);
+    __SetAttribute(el, "__lynx_source_location", {
+        file: "src/App.jsx",
+        line: 3,
+        column: 38,
+        component: "App"
+    });
+    return [
+        el
+    ];
+}, null, require('@lynx-js/react/internal').__DynamicPartChildren_0, undefined, globDynamicComponentEntry);
+const __snapshot_c0e9e_App_0_8ec8fdc5 = /*#__PURE__*/ require('@lynx-js/react/internal').createSnapshot("__snapshot_c0e9e_App_0_8ec8fdc5", function() {
+    const pageId = require('@lynx-js/react/internal').__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateText(pageId);
+    __AppendElement(el, el1);
+    __SetAttribute(el, "__lynx_source_location", {
+        file: "src/App.jsx",
+        line: 5,
+        column: 9,
+        component: "App"
+    });
+    __SetAttribute(el1, "__lynx_source_location", {
+        file: "src/App.jsx",
+        line: 6,
+        column: 11,
+        component: "App"
+    });
+    return [
+        el,
+        el1
+    ];
+}, null, [
+    [
+        require('@lynx-js/react/internal').__DynamicPartChildren,
+        1
+    ]
+], undefined, globDynamicComponentEntry);
+function App() {
+    const items = list.map((item)=><__snapshot_c0e9e_App_items_0_00e76d20>{item}</__snapshot_c0e9e_App_items_0_00e76d20>);
+    return <__snapshot_c0e9e_App_0_8ec8fdc5>{title}</__snapshot_c0e9e_App_0_8ec8fdc5>;
+}
+const __snapshot_c0e9e_Footer_0_8ec8fdc5 = /*#__PURE__*/ require('@lynx-js/react/internal').createSnapshot("__snapshot_c0e9e_Footer_0_8ec8fdc5", function() {
+    const pageId = require('@lynx-js/react/internal').__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateText(pageId);
+    __AppendElement(el, el1);
+    __SetAttribute(el, "__lynx_source_location", {
+        file: "src/App.jsx",
+        line: 10,
+        column: 26,
+        component: "Footer"
+    });
+    __SetAttribute(el1, "__lynx_source_location", {
+        file: "src/App.jsx",
+        line: 10,
+        column: 32,
+        component: "Footer"
+    });
+    return [
+        el,
+        el1
+    ];
+}, null, [
+    [
+        require('@lynx-js/react/internal').__DynamicPartChildren,
+        1
+    ]
+], undefined, globDynamicComponentEntry);
+const Footer = ()=><__snapshot_c0e9e_Footer_0_8ec8fdc5>{title}</__snapshot_c0e9e_Footer_0_8ec8fdc5>;
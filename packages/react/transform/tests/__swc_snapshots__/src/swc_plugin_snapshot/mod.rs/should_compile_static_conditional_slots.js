@@ -0,0 +1,112 @@
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_2", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateText(pageId);
+    const el1 = __CreateRawText("Not boolean");
+    __AppendElement(el, el1);
+    return [
+        el,
+        el1
+    ];
+}, null, null, undefined, globDynamicComponentEntry);
+const __snapshot_da39a_test_3 = ReactLynx.createSnapshot("__snapshot_da39a_test_3", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateText(pageId);
+    return [
+        el
+    ];
+}, null, ReactLynx.__DynamicPartChildren_0, undefined, globDynamicComponentEntry);
+const __snapshot_da39a_test_4 = ReactLynx.createSnapshot("__snapshot_da39a_test_4", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateText(pageId);
+    const el1 = __CreateRawText("Dynamic");
+    __AppendElement(el, el1);
+    return [
+        el,
+        el1
+    ];
+}, null, null, undefined, globDynamicComponentEntry);
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateWrapperElement(pageId);
+    __AppendElement(el, el1);
+    const el2 = __CreateText(pageId);
+    __SetClasses(el2, "loading");
+    const el3 = __CreateRawText("Loading");
+    __AppendElement(el2, el3);
+    const el4 = __CreateView(pageId);
+    const el5 = __CreateText(pageId);
+    __AppendElement(el4, el5);
+    const el6 = __CreateRawText("Done");
+    __AppendElement(el5, el6);
+    const el7 = __CreateWrapperElement(pageId);
+    __AppendElement(el, el7);
+    const el8 = __CreateText(pageId);
+    const el9 = __CreateRawText("Has items");
+    __AppendElement(el8, el9);
+    const el10 = __CreateWrapperElement(pageId);
+    __AppendElement(el, el10);
+    const el11 = __CreateImage(pageId);
+    __SetAttribute(el11, "src", "ok.png");
+    const el12 = __CreateWrapperElement(pageId);
+    __AppendElement(el, el12);
+    return [
+        el,
+        el1,
+        el2,
+        el3,
+        el4,
+        el5,
+        el6,
+        el7,
+        el8,
+        el9,
+        el10,
+        el11,
+        el12
+    ];
+}, [
+    function(ctx, index, oldValue) {
+        if (ctx.__elements) {
+            if (oldValue !== undefined) {
+                __RemoveElement(ctx.__elements[1], ctx.__elements[oldValue ? 2 : 4]);
+            }
+            __AppendElement(ctx.__elements[1], ctx.__elements[ctx.__values[0] ? 2 : 4]);
+        }
+    },
+    function(ctx, index, oldValue) {
+        if (ctx.__elements) {
+            if (oldValue) {
+                __RemoveElement(ctx.__elements[7], ctx.__elements[8]);
+            }
+            if (ctx.__values[1]) {
+                __AppendElement(ctx.__elements[7], ctx.__elements[8]);
+            }
+        }
+    },
+    function(ctx, index, oldValue) {
+        if (ctx.__elements) {
+            if (oldValue) {
+                __RemoveElement(ctx.__elements[10], ctx.__elements[11]);
+            }
+            if (ctx.__values[2]) {
+                __AppendElement(ctx.__elements[10], ctx.__elements[11]);
+            }
+        }
+    }
+], [
+    [
+        ReactLynx.__DynamicPartChildren,
+        12
+    ]
+], undefined, globDynamicComponentEntry);
+<__snapshot_da39a_test_1 values={[
+    !!loading,
+    count > 0,
+    !error
+]}>{[
+    visible && <__snapshot_da39a_test_2/>,
+    loading ? <__snapshot_da39a_test_3>{title}</__snapshot_da39a_test_3> : <__snapshot_da39a_test_4/>,
+    loading ? <Spinner/> : null
+]}</__snapshot_da39a_test_1>;
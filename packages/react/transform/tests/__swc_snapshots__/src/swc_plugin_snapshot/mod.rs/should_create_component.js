@@ -0,0 +1,37 @@
+import * as ReactLynx from "@lynx-js/react";
+const NAME = 'header';
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateComponent(pageId, "", 0, "", "header", "./header", {}, {});
+    __SetClasses(el1, "a");
+    __AppendElement(el, el1);
+    const el2 = __CreateText(pageId);
+    __AppendElement(el1, el2);
+    const el3 = __CreateComponent(pageId, "", 0, "", "", "", {}, {});
+    __SetAttribute(el3, "flatten", false);
+    __AppendElement(el, el3);
+    const el4 = __CreateComponent(pageId, "", 0, "", "", "", {}, {});
+    __AppendElement(el, el4);
+    return [
+        el,
+        el1,
+        el2,
+        el3,
+        el4
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetAttribute(ctx.__elements[3], "name", ctx.__values[0]);
+        }
+    }
+], [
+    [
+        ReactLynx.__DynamicPartChildren,
+        2
+    ]
+], undefined, globDynamicComponentEntry);
+<__snapshot_da39a_test_1 values={[
+    name
+]}>{title}</__snapshot_da39a_test_1>;
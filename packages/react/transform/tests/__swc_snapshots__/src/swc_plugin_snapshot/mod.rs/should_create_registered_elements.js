@@ -0,0 +1,49 @@
+import * as ReactLynx from "@lynx-js/react";
+const PROVIDER = 'amap';
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateMap(pageId, {
+        "provider": "amap",
+        "lite-mode": true
+    });
+    __AppendElement(el, el1);
+    const el2 = __CreateMap(pageId);
+    __SetAttribute(el2, "provider", "amap");
+    __AppendElement(el, el2);
+    const el3 = __CreateRichText(pageId);
+    __AppendElement(el, el3);
+    const el4 = __CreateRawText("Hello, ");
+    __AppendElement(el3, el4);
+    const el5 = __CreateWrapperElement(pageId);
+    __AppendElement(el3, el5);
+    return [
+        el,
+        el1,
+        el2,
+        el3,
+        el4,
+        el5
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetAttribute(ctx.__elements[1], "zoom", ctx.__values[0]);
+        }
+    },
+    (snapshot, index, oldValue)=>ReactLynx.updateSpread(snapshot, index, oldValue, 2, [
+            "provider"
+        ])
+], [
+    [
+        ReactLynx.__DynamicPartChildren,
+        5
+    ]
+], undefined, globDynamicComponentEntry);
+<__snapshot_da39a_test_1 values={[
+    zoom,
+    {
+        ...props,
+        __spread: true
+    }
+]}>{name}</__snapshot_da39a_test_1>;
@@ -46,20 +46,7 @@ const __snapshot_da39a_test_3 = /*#__PURE__*/ ReactLynx.createSnapshot("__snapsh
         "100rpx"
     ]
 ]}/>;
-const __snapshot_da39a_test_4 = /*#__PURE__*/ ReactLynx.createSnapshot("__snapshot_da39a_test_4", function() {
-    const pageId = ReactLynx.__pageId;
-    const el = __CreateView(pageId);
-    return [
-        el
-    ];
-}, [
-    function(ctx) {
-        if (ctx.__elements) {
-            __SetInlineStyles(ctx.__elements[0], ctx.__values[0]);
-        }
-    }
-], null, undefined, globDynamicComponentEntry);
-<__snapshot_da39a_test_4 values={[
+<__snapshot_da39a_test_2 values={[
     {
         backgroundColor: "red",
         ...style
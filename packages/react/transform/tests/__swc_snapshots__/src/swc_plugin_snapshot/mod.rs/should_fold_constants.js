@@ -0,0 +1,76 @@
+import * as ReactLynx from "@lynx-js/react";
+const BASE = 50;
+export const PREFIX = 'a';
+let mutable = 'b';
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    __AddInlineStyle(el, 27, "100px");
+    return [
+        el
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            let el = ctx.__elements[0];
+            let style_values = ctx.__values[0];
+            __AddInlineStyle(el, 26, style_values[0]), __AddInlineStyle(el, 51, style_values[1]);
+        }
+    }
+], null, undefined, globDynamicComponentEntry);
+<__snapshot_da39a_test_1 values={[
+    [
+        h,
+        -0.5
+    ]
+]}/>;
+const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_2", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    __SetInlineStyles(el, "width: 50rpx;");
+    __SetClasses(el, "a-50");
+    __SetID(el, "a1");
+    __AddDataset(el, "base", 50);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry);
+<__snapshot_da39a_test_2/>;
+const __snapshot_da39a_test_3 = ReactLynx.createSnapshot("__snapshot_da39a_test_3", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    return [
+        el
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetClasses(ctx.__elements[0], ctx.__values[0] || '');
+        }
+    }
+], null, undefined, globDynamicComponentEntry);
+<__snapshot_da39a_test_3 values={[
+    `${PREFIX} ${mutable}`
+]}/>;
+const __snapshot_da39a_test_4 = ReactLynx.createSnapshot("__snapshot_da39a_test_4", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    return [
+        el
+    ];
+}, [
+    function(ctx) {
+        if (ctx.__elements) {
+            let el = ctx.__elements[0];
+            let style_values = ctx.__values[0];
+            __AddInlineStyle(el, 27, style_values[0]);
+        }
+    }
+], null, undefined, globDynamicComponentEntry);
+function App(BASE) {
+    return <__snapshot_da39a_test_4 values={[
+        [
+            BASE + 'px'
+        ]
+    ]}/>;
+}
@@ -0,0 +1,35 @@
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_2", null, null, ReactLynx.__DynamicPartChildren_0, undefined, globDynamicComponentEntry, function(ctx, r) {
+    return "<text>" + r.slot(ctx, 0) + "</text>";
+});
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", null, null, [
+    [
+        ReactLynx.__DynamicPartSlot,
+        5
+    ],
+    [
+        ReactLynx.__DynamicPartSlot,
+        6
+    ]
+], undefined, globDynamicComponentEntry, function(ctx, r) {
+    return '<view class="a" id="b" data-x="1" style="width:100px;color:red"><text>Hello &amp; &quot;world&quot;</text><image' + r.attr("src", ctx.__values[0]) + r.attr("class", ctx.__values[1]) + r.style("", ctx.__values[2], [
+        "height",
+        , 
+    ]) + '></image><view class="c"' + r.spread(ctx.__values[4], [
+        "className"
+    ]) + "></view><wrapper>" + r.slot(ctx, 0) + "</wrapper><wrapper>" + r.slot(ctx, 1) + "</wrapper></view>";
+});
+<__snapshot_da39a_test_1 values={[
+    src,
+    cls,
+    [
+        h,
+        w
+    ],
+    onTap,
+    {
+        ...props,
+        __spread: true
+    }
+]}><wrapper>{child}
+      </wrapper><__snapshot_da39a_test_2>{name}</__snapshot_da39a_test_2></__snapshot_da39a_test_1>;
@@ -0,0 +1,8 @@
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", null, null, null, undefined, globDynamicComponentEntry, function(ctx, r) {
+    return "<view><wrapper>" + (ctx.__values[0] ? '<text class="loading">Loading</text>' : "<view><text>Done</text></view>") + "</wrapper><wrapper>" + (ctx.__values[1] ? "<text>Has items</text>" : "") + "</wrapper></view>";
+});
+<__snapshot_da39a_test_1 values={[
+    !!loading,
+    count > 0
+]}/>;
@@ -6,13 +6,6 @@ const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_
         el
     ];
 }, null, ReactLynx.__DynamicPartChildren_0, undefined, globDynamicComponentEntry);
-const __snapshot_da39a_test_3 = ReactLynx.createSnapshot("__snapshot_da39a_test_3", function() {
-    const pageId = ReactLynx.__pageId;
-    const el = __CreateText(pageId);
-    return [
-        el
-    ];
-}, null, ReactLynx.__DynamicPartChildren_0, undefined, globDynamicComponentEntry);
 const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
     const pageId = ReactLynx.__pageId;
     const el = __CreateView(pageId);
@@ -49,4 +42,4 @@ const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_
     ]
 ], undefined, globDynamicComponentEntry);
 <__snapshot_da39a_test_1><wrapper>{hello}</wrapper><wrapper><__snapshot_da39a_test_2 key={hello}>{hello}</__snapshot_da39a_test_2>
-      </wrapper><__snapshot_da39a_test_3 key="hello">{hello}</__snapshot_da39a_test_3></__snapshot_da39a_test_1>;
+      </wrapper><__snapshot_da39a_test_2 key="hello">{hello}</__snapshot_da39a_test_2></__snapshot_da39a_test_1>;
@@ -0,0 +1,94 @@
+import { jsx as _jsx } from "@lynx-js/react/jsx-runtime";
+import * as ReactLynx from "@lynx-js/react";
+const __snapshot_da39a_test_2 = ReactLynx.createSnapshot("__snapshot_da39a_test_2", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateElement("list-item", pageId);
+    __SetAttribute(el, "name", "bar");
+    __SetAttribute(el, "flatten", false);
+    return [
+        el
+    ];
+}, [
+    (snapshot, index, oldValue)=>ReactLynx.updateSpread(snapshot, index, oldValue, 0, [
+            "name",
+            "flatten"
+        ])
+], null, undefined, globDynamicComponentEntry);
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function(snapshotInstance) {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    const el1 = __CreateView(pageId);
+    __SetInlineStyles(el1, "color:red");
+    __AppendElement(el, el1);
+    const el2 = __CreateText(pageId);
+    __AppendElement(el1, el2);
+    const el3 = __CreateRawText("!!!");
+    __AppendElement(el2, el3);
+    const el4 = ReactLynx.snapshotCreateList(pageId, snapshotInstance, 4);
+    __AppendElement(el, el4);
+    return [
+        el,
+        el1,
+        el2,
+        el3,
+        el4
+    ];
+}, [
+    (snapshot, index, oldValue)=>ReactLynx.updateSpread(snapshot, index, oldValue, 1, [
+            "className",
+            "style",
+            "id",
+            "bindtap",
+            "ref"
+        ]),
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetClasses(ctx.__elements[1], ctx.__values[1] || '');
+        }
+    },
+    function(ctx) {
+        if (ctx.__elements) {
+            __SetID(ctx.__elements[1], ctx.__values[2]);
+        }
+    },
+    (snapshot, index, oldValue)=>ReactLynx.updateEvent(snapshot, index, oldValue, 1, "bindEvent", "tap", ''),
+    (snapshot, index, oldValue)=>ReactLynx.updateRef(snapshot, index, oldValue, 1, ''),
+    (snapshot, index, oldValue)=>ReactLynx.updateSpread(snapshot, index, oldValue, 2, [
+            "main-thread:bindtap"
+        ]),
+    (snapshot, index, oldValue)=>ReactLynx.updateWorkletEvent(snapshot, index, oldValue, 2, "main-thread", "bindEvent", "tap")
+], [
+    [
+        ReactLynx.__DynamicPartListChildren,
+        4
+    ]
+], undefined, globDynamicComponentEntry);
+_jsx(__snapshot_da39a_test_1, {
+    values: [
+        {
+            ...a,
+            "class": "foo",
+            ...b,
+            "data-x": "x",
+            __spread: true
+        },
+        cls,
+        id,
+        1,
+        ReactLynx.transformRef(ref),
+        {
+            ...c,
+            __spread: true
+        },
+        onMTTap
+    ],
+    children: _jsx(__snapshot_da39a_test_2, {
+        values: [
+            {
+                ...d,
+                "item-key": key,
+                __spread: true
+            }
+        ]
+    })
+});
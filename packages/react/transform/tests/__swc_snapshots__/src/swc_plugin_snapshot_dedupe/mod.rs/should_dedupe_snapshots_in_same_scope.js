@@ -0,0 +1,26 @@
+const __snapshot_6f4a5c2e1b3d7a90 = ReactLynx.createSnapshot("__snapshot_6f4a5c2e1b3d7a90", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateView(pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry);
+const __snapshot_6f4a5c2e1b3d7a901 = __snapshot_6f4a5c2e1b3d7a90;
+const __snapshot_da39a_test_1 = ReactLynx.createSnapshot("__snapshot_da39a_test_1", function() {
+    const pageId = ReactLynx.__pageId;
+    const el = __CreateText(pageId);
+    return [
+        el
+    ];
+}, null, null, undefined, globDynamicComponentEntry);
+function lazy() {
+    // Another scope, which may be evaluated before the outer one.
+    const __snapshot_6f4a5c2e1b3d7a902 = ReactLynx.createSnapshot("__snapshot_6f4a5c2e1b3d7a90", function() {
+        const pageId = ReactLynx.__pageId;
+        const el = __CreateView(pageId);
+        return [
+            el
+        ];
+    }, null, null, undefined, globDynamicComponentEntry);
+    const __snapshot_6f4a5c2e1b3d7a903 = __snapshot_6f4a5c2e1b3d7a902;
+}
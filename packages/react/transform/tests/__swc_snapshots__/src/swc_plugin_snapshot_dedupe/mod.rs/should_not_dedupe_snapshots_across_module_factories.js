@@ -0,0 +1,35 @@
+var __webpack_modules__ = {
+    "./a.jsx": function(module, __webpack_exports__, __webpack_require__) {
+        var _react__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("@lynx-js/react");
+        const __snapshot_6f4a5c2e1b3d7a90 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+            const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+            const el = __CreateView(pageId);
+            return [
+                el
+            ];
+        }, null, null, undefined, globDynamicComponentEntry);
+    },
+    "./b.jsx": function(module, __webpack_exports__, __webpack_require__) {
+        var _react__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("@lynx-js/react");
+        // Not merged, it is in the scope of another module.
+        const __snapshot_6f4a5c2e1b3d7a90 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+            const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+            const el = __CreateView(pageId);
+            return [
+                el
+            ];
+        }, null, null, undefined, globDynamicComponentEntry);
+    },
+    "./c.jsx + 1 modules": function(module, __webpack_exports__, __webpack_require__) {
+        var _react__WEBPACK_IMPORTED_MODULE_0__ = __webpack_require__("@lynx-js/react");
+        // The concatenated modules share a scope, they are merged.
+        const __snapshot_6f4a5c2e1b3d7a90 = (0, _react__WEBPACK_IMPORTED_MODULE_0__.createSnapshot)("__snapshot_6f4a5c2e1b3d7a90", function() {
+            const pageId = _react__WEBPACK_IMPORTED_MODULE_0__.__pageId;
+            const el = __CreateView(pageId);
+            return [
+                el
+            ];
+        }, null, null, undefined, globDynamicComponentEntry);
+        const __snapshot_6f4a5c2e1b3d7a901 = __snapshot_6f4a5c2e1b3d7a90;
+    }
+};
@@ -0,0 +1,26 @@
+import { loadWorkletRuntime as __loadWorkletRuntime } from "@lynx-js/react";
+var loadWorkletRuntime = __loadWorkletRuntime;
+import { registerAnimationWorklet as __registerAnimationWorklet } from "@lynx-js/animation";
+var registerAnimationWorklet = __registerAnimationWorklet;
+const onFrame = {
+    _c: {
+        ref
+    },
+    _lepusWorkletHash: "a123:test:1"
+};
+const onTap = {
+    _c: {
+        color
+    },
+    _lepusWorkletHash: "a123:test:2"
+};
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerAnimationWorklet("animation", "a123:test:1", function(progress) {
+    let { ref } = this["_c"];
+    'use animation';
+    ref.current.setStyleProperty('opacity', progress);
+});
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "a123:test:2", function(e) {
+    let { color } = this["_c"];
+    'main thread';
+    e.currentTarget.setStyleProperty('color', color);
+});
@@ -0,0 +1,36 @@
+import { loadWorkletRuntime as __loadWorkletRuntime } from "@lynx-js/react";
+var loadWorkletRuntime = __loadWorkletRuntime;
+const onTapA = {
+    _c: {
+        color
+    },
+    _lepusWorkletHash: "5ab18b94eecc8467"
+};
+const onTapB = {
+    _c: {
+        color
+    },
+    _lepusWorkletHash: "5ab18b94eecc8467"
+};
+let onTapC = {
+    _c: {
+        color
+    },
+    _lepusWorkletHash: "e89ac8a6479252cc"
+};
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "5ab18b94eecc8467", function(e) {
+    let { color } = this["_c"];
+    'main thread';
+    e.currentTarget.setStyleProperty('color', color);
+});
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "5ab18b94eecc8467", function(e) {
+    let { color } = this["_c"];
+    'main thread';
+    e.currentTarget.setStyleProperty('color', color);
+});
+loadWorkletRuntime(typeof globDynamicComponentEntry === 'undefined' ? undefined : globDynamicComponentEntry) && registerWorkletInternal("main-thread", "e89ac8a6479252cc", function(event) {
+    const onTapC = lynxWorkletImpl._workletMap["e89ac8a6479252cc"].bind(this);
+    let { color } = this["_c"];
+    'main thread';
+    event.currentTarget.setStyleProperty('color', color);
+});